@@ -0,0 +1,48 @@
+//! Times looking guesses up in a large word list, against scanning the
+//! list the way the game used to. Run it with `cargo bench --bench lookup`.
+
+use rustle::RustleGame;
+use std::hint::black_box;
+use std::time::Instant;
+
+const WORDS: usize = 100_000;
+const LOOKUPS: usize = 1000;
+
+fn main() {
+    let words: Vec<String> = (0..WORDS).map(word).collect();
+    let game = RustleGame::from_word_lists(words.clone(), words.clone(), 5)
+        .expect("the word list isn't empty");
+    // Half of the guesses are in the list, spread over it, and half aren't.
+    let guesses: Vec<String> = (0..LOOKUPS)
+        .map(|i| word(i * WORDS * 2 / LOOKUPS))
+        .collect();
+
+    let started = Instant::now();
+    let found = guesses
+        .iter()
+        .filter(|guess| game.is_valid_guess(black_box(guess)))
+        .count();
+    let set = started.elapsed();
+
+    let started = Instant::now();
+    let scanned = guesses
+        .iter()
+        .filter(|guess| words.iter().any(|word| word == black_box(*guess)))
+        .count();
+    let scan = started.elapsed();
+
+    assert_eq!(found, scanned);
+    println!("{LOOKUPS} lookups in {WORDS} words, {found} found:");
+    println!("  is_valid_guess: {set:.2?}");
+    println!("  scanning a Vec: {scan:.2?}");
+}
+
+/// The `n`th five-letter word counting from AAAAA.
+fn word(mut n: usize) -> String {
+    let mut letters = [b'A'; 5];
+    for letter in letters.iter_mut().rev() {
+        *letter += (n % 26) as u8;
+        n /= 26;
+    }
+    String::from_utf8(letters.to_vec()).unwrap()
+}
@@ -0,0 +1,51 @@
+//! Compares starting each game from scratch with the builder against
+//! reusing one game with `reset_with_new_word`, with the solver playing
+//! every game. Run it with `cargo bench --bench reset`, or with e.g.
+//! `cargo bench --bench reset -- 200` to play only 200 games each way.
+
+use bracket_random::prelude::RandomNumberGenerator;
+use rustle::RustleGame;
+use std::time::{Duration, Instant};
+
+const GAMES: usize = 2000;
+
+fn main() {
+    // Cargo passes `--bench` along with whatever follows `--`.
+    let games = std::env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse::<usize>().ok())
+        .filter(|&games| games > 0)
+        .unwrap_or(GAMES);
+
+    let mut setup = Duration::ZERO;
+    let started = Instant::now();
+    for seed in 0..games as u64 {
+        let building = Instant::now();
+        let mut game = RustleGame::builder()
+            .seed(seed)
+            .build()
+            .expect("the embedded word list is valid");
+        setup += building.elapsed();
+        game.autoplay();
+    }
+    report("building each game", games, started.elapsed(), setup);
+
+    let mut rng = RandomNumberGenerator::seeded(0);
+    let mut game = RustleGame::builder()
+        .seed(0)
+        .build()
+        .expect("the embedded word list is valid");
+    let mut setup = Duration::ZERO;
+    let started = Instant::now();
+    for _ in 0..games {
+        let resetting = Instant::now();
+        game.reset_with_new_word(&mut rng);
+        setup += resetting.elapsed();
+        game.autoplay();
+    }
+    report("reset_with_new_word", games, started.elapsed(), setup);
+}
+
+fn report(how: &str, games: usize, total: Duration, setup: Duration) {
+    println!("{games} games after {how}: {total:.2?}, of which setup {setup:.2?}");
+}
@@ -0,0 +1,57 @@
+//! Lets the solver play every answer of the embedded dictionary and reports
+//! how many guesses it needed. Run it with `cargo bench`, or with e.g.
+//! `cargo bench -- 1000` to play only 1000 answers spread over the list.
+
+use rustle::tournament::Tournament;
+use rustle::{words_list, RustleGame, MAX_TRIES, WORD_LENGTH};
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+fn main() {
+    let answers = words_list(WORD_LENGTH);
+    // Cargo passes `--bench` along with whatever follows `--`.
+    let sample = std::env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse::<usize>().ok())
+        .filter(|&sample| sample > 0);
+    let step = sample.map_or(1, |sample| (answers.len() / sample).max(1));
+
+    let started = Instant::now();
+    let mut tournament = Tournament::default();
+    for answer in answers
+        .iter()
+        .step_by(step)
+        .take(sample.unwrap_or(usize::MAX))
+    {
+        let mut game = RustleGame::replay_of(answer.clone(), answers.clone());
+        game.autoplay();
+        tournament.record(&game);
+    }
+    let elapsed = started.elapsed();
+
+    let mut distribution: BTreeMap<usize, usize> = BTreeMap::new();
+    let mut lost = 0;
+    for round in tournament.rounds() {
+        match round.tries {
+            Some(tries) => *distribution.entry(tries).or_insert(0) += 1,
+            None => lost += 1,
+        }
+    }
+    let games = tournament.rounds().len();
+    #[allow(clippy::cast_precision_loss)]
+    let win_rate = 100.0 * tournament.wins() as f64 / games as f64;
+
+    println!("Solver over {games} answers, {MAX_TRIES} tries each, in {elapsed:.2?}");
+    println!("Win rate: {win_rate:.1}%");
+    if let Some(mean) = tournament.average_tries() {
+        println!("Mean guesses per win: {mean:.3}");
+    }
+    if let Some(max) = distribution.keys().next_back() {
+        println!("Max guesses per win: {max}");
+    }
+    println!("Guesses:");
+    for (tries, count) in &distribution {
+        println!("{tries:>3}: {count}");
+    }
+    println!("  X: {lost}");
+}
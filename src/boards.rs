@@ -0,0 +1,275 @@
+//! Several hidden words guessed at once, Dordle style: every guess is
+//! scored on each board, and the game is won once every board is solved.
+
+use crate::render::visible_width;
+use crate::{GameStatus, InvalidGuess, RustleGame};
+use bracket_random::prelude::RandomNumberGenerator;
+use std::io::{self, Write};
+
+/// How many tries there are on top of one per board.
+pub const EXTRA_TRIES: usize = 5;
+
+/// The spaces between two boards shown side by side.
+const GAP: &str = "    ";
+
+/// Boards that share every guess. A board that is solved is locked: it
+/// takes no more guesses, and keeps showing the rows it was solved in.
+pub struct Boards {
+    boards: Vec<RustleGame>,
+    tries: usize,
+}
+
+impl Boards {
+    /// Starts `count` boards from `first`, with the same word lists and
+    /// settings but each with its own word picked by `rng`. Every board gets
+    /// [`EXTRA_TRIES`] more tries than there are boards.
+    #[must_use]
+    pub fn new(first: RustleGame, count: usize, rng: &mut RandomNumberGenerator) -> Self {
+        let max_tries = count + EXTRA_TRIES;
+        let mut boards = vec![first.with_max_tries(max_tries)];
+        while boards.len() < count {
+            let mut next = boards[boards.len() - 1].replay();
+            next.reset_with_new_word(rng);
+            boards.push(next);
+        }
+        Boards { boards, tries: 0 }
+    }
+
+    #[must_use]
+    pub fn boards(&self) -> &[RustleGame] {
+        &self.boards
+    }
+
+    /// How many guesses have been made.
+    #[must_use]
+    pub fn tries(&self) -> usize {
+        self.tries
+    }
+
+    #[must_use]
+    pub fn max_tries(&self) -> usize {
+        self.boards[0].max_tries()
+    }
+
+    /// Plays raw player input on every board that isn't solved yet, and
+    /// returns where the game stands.
+    ///
+    /// # Errors
+    ///
+    /// Returns why the guess was rejected by one of the open boards, in
+    /// which case nothing is recorded.
+    pub fn submit(&mut self, input: &str) -> Result<GameStatus, InvalidGuess> {
+        let open: Vec<usize> = (0..self.boards.len())
+            .filter(|&i| !is_solved(&self.boards[i]))
+            .collect();
+        let Some(&first) = open.first() else {
+            return Ok(self.check_status());
+        };
+        let guess = self.boards[first].explore(input)?.word;
+        for &i in &open {
+            self.boards[i].validate(&guess)?;
+        }
+        for &i in &open {
+            self.boards[i].guess(&guess);
+        }
+        self.tries += 1;
+        Ok(self.check_status())
+    }
+
+    /// Won once every board is solved, and lost when the tries run out
+    /// first, with the words that weren't found.
+    #[must_use]
+    pub fn check_status(&self) -> GameStatus {
+        if self.boards.iter().all(is_solved) {
+            GameStatus::Won { tries: self.tries }
+        } else if self.tries >= self.max_tries() {
+            let missed: Vec<&str> = self
+                .boards
+                .iter()
+                .filter(|board| !is_solved(board))
+                .map(RustleGame::word)
+                .collect();
+            GameStatus::Lost {
+                word: missed.join(", "),
+            }
+        } else {
+            GameStatus::InProgress
+        }
+    }
+
+    /// Every board's word, to show when the player gives up, or `None` if
+    /// the words are hidden.
+    #[must_use]
+    pub fn revealed_words(&self) -> Option<String> {
+        let words: Option<Vec<&str>> = self.boards.iter().map(RustleGame::revealed_word).collect();
+        words.map(|words| words.join(", "))
+    }
+
+    /// What to tell the player when the tries run out: the words that
+    /// weren't found, unless they are hidden.
+    #[must_use]
+    pub fn loss_message(&self) -> String {
+        match self.check_status() {
+            GameStatus::Lost { word } if self.boards[0].revealed_word().is_some() => {
+                format!("Out of tries! You missed {word}")
+            }
+            _ => "Out of tries!".to_string(),
+        }
+    }
+
+    /// Renders the boards side by side, each under a numbered heading that
+    /// says once it is solved.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let columns: Vec<Vec<String>> = self
+            .boards
+            .iter()
+            .enumerate()
+            .map(|(i, board)| {
+                let heading = if is_solved(board) {
+                    format!("Board {} (solved)", i + 1)
+                } else {
+                    format!("Board {}", i + 1)
+                };
+                std::iter::once(heading)
+                    .chain(board.render_board().lines().map(String::from))
+                    .collect()
+            })
+            .collect();
+        let rows = columns.iter().map(Vec::len).max().unwrap_or(0);
+        let widths: Vec<usize> = columns
+            .iter()
+            .map(|column| {
+                column
+                    .iter()
+                    .map(|line| visible_width(line))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let mut out = String::new();
+        for row in 0..rows {
+            let mut line = String::new();
+            for (column, width) in columns.iter().zip(&widths) {
+                if !line.is_empty() {
+                    line.push_str(GAP);
+                }
+                let cell = column.get(row).map_or("", String::as_str);
+                line.push_str(cell);
+                line.push_str(&" ".repeat(width - visible_width(cell)));
+            }
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Prints the boards side by side to stdout.
+    ///
+    /// # Panics
+    ///
+    /// Panics if writing to stdout fails.
+    pub fn display_guesses(&self) {
+        self.write_guesses(&mut io::stdout())
+            .expect("couldn't write to stdout");
+    }
+
+    /// Writes the boards side by side.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `out` fails.
+    pub fn write_guesses(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "{}", self.render())
+    }
+}
+
+fn is_solved(board: &RustleGame) -> bool {
+    matches!(board.check_status(), GameStatus::Won { .. })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RenderMode;
+
+    fn boards() -> Boards {
+        let dictionary: Vec<String> = ["CRANE", "GRACE", "SLOTH"].map(String::from).into();
+        let first = RustleGame::replay_of("CRANE".to_string(), dictionary);
+        Boards::new(first, 2, &mut RandomNumberGenerator::seeded(1))
+    }
+
+    #[test]
+    fn test_boards_have_different_words() {
+        let boards = boards();
+        assert_eq!(boards.boards().len(), 2);
+        assert_eq!(boards.max_tries(), 2 + EXTRA_TRIES);
+        assert_ne!(boards.boards()[0].word(), boards.boards()[1].word());
+        assert!(boards.boards().iter().all(|b| b.max_tries() == 7));
+    }
+
+    #[test]
+    fn test_solved_board_is_locked() {
+        let mut boards = boards();
+        let second = boards.boards()[1].word().to_string();
+        assert_eq!(boards.submit("crane"), Ok(GameStatus::InProgress));
+        assert_eq!(
+            boards.submit("xyzzy"),
+            Err(InvalidGuess::NotInDictionary("XYZZY".to_string()))
+        );
+        assert_eq!(boards.tries(), 1);
+        assert_eq!(boards.submit(&second), Ok(GameStatus::Won { tries: 2 }));
+        assert_eq!(boards.boards()[0].guesses().len(), 1);
+        assert_eq!(boards.boards()[1].guesses().len(), 2);
+    }
+
+    #[test]
+    fn test_lost_names_the_missed_words() {
+        let mut boards = boards();
+        let second = boards.boards()[1].word().to_string();
+        let wrong = if second == "SLOTH" { "GRACE" } else { "SLOTH" };
+        boards.submit("crane").unwrap();
+        for _ in 1..boards.max_tries() {
+            boards.submit(wrong).unwrap();
+        }
+        assert_eq!(
+            boards.check_status(),
+            GameStatus::Lost {
+                word: second.clone()
+            }
+        );
+        assert_eq!(
+            boards.loss_message(),
+            format!("Out of tries! You missed {second}")
+        );
+    }
+
+    #[test]
+    fn test_hidden_words_stay_hidden() {
+        let dictionary: Vec<String> = ["CRANE", "GRACE", "SLOTH"].map(String::from).into();
+        let first = RustleGame::replay_of("CRANE".to_string(), dictionary).with_hide_answer(true);
+        let mut boards = Boards::new(first, 2, &mut RandomNumberGenerator::seeded(1));
+        assert_eq!(boards.revealed_words(), None);
+        for _ in 0..boards.max_tries() {
+            boards.submit("crane").unwrap();
+        }
+        assert!(matches!(boards.check_status(), GameStatus::Lost { .. }));
+        assert_eq!(boards.loss_message(), "Out of tries!");
+    }
+
+    #[test]
+    fn test_render_side_by_side() {
+        let dictionary: Vec<String> = ["CRANE", "GRACE", "SLOTH"].map(String::from).into();
+        let first = RustleGame::replay_of("CRANE".to_string(), dictionary)
+            .with_render_mode(RenderMode::Symbols);
+        let mut boards = Boards::new(first, 2, &mut RandomNumberGenerator::seeded(1));
+        boards.submit("crane").unwrap();
+        let render = boards.render();
+        let lines: Vec<&str> = render.lines().collect();
+        assert_eq!(lines.len(), 1 + boards.max_tries());
+        assert!(lines[0].starts_with("Board 1 (solved)"));
+        assert!(lines[0].ends_with("Board 2"));
+        assert_eq!(lines[1].matches('C').count(), 2);
+    }
+}
@@ -0,0 +1,542 @@
+//! Setting up a game with many options at once.
+
+use crate::{
+    daily_word, non_empty, random_word, sanitize_phrase, today, words_list, Charset, GameError,
+    Mode, Numbering, RenderMode, RustleGame, Theme, MAX_TRIES, WORD_LENGTH,
+};
+use bracket_random::prelude::RandomNumberGenerator;
+use std::collections::HashSet;
+
+/// Collects the options for a [`RustleGame`] and starts it with
+/// [`build`](RustleGameBuilder::build). The builder can be reused to start
+/// several games with the same options.
+///
+/// ```
+/// let game = rustle::RustleGame::builder()
+///     .length(6)
+///     .max_tries(8)
+///     .hard_mode(true)
+///     .build()
+///     .unwrap();
+/// assert_eq!(game.word_length(), 6);
+/// ```
+#[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct RustleGameBuilder {
+    length: usize,
+    max_tries: usize,
+    zen: bool,
+    hard_mode: bool,
+    theme: Theme,
+    render_mode: RenderMode,
+    numbering: Numbering,
+    mode: Mode,
+    charset: Charset,
+    timed: bool,
+    frequency_hints: bool,
+    assist: bool,
+    coach: bool,
+    distinct_first: bool,
+    peeks: bool,
+    allow_any: bool,
+    position_hints: bool,
+    hints_enabled: bool,
+    hide_answer: bool,
+    legend: bool,
+    daily: bool,
+    seed: Option<u64>,
+    dictionary: Option<Vec<String>>,
+    answers: Option<Vec<String>>,
+    phrases: Option<Vec<String>>,
+    word: Option<String>,
+    used_words: HashSet<String>,
+    weights: Vec<f64>,
+}
+
+impl Default for RustleGameBuilder {
+    fn default() -> Self {
+        Self {
+            length: WORD_LENGTH,
+            max_tries: MAX_TRIES,
+            zen: false,
+            hard_mode: false,
+            theme: Theme::default(),
+            render_mode: RenderMode::default(),
+            numbering: Numbering::default(),
+            mode: Mode::default(),
+            charset: Charset::default(),
+            timed: false,
+            frequency_hints: false,
+            assist: false,
+            coach: false,
+            distinct_first: false,
+            peeks: false,
+            allow_any: false,
+            position_hints: false,
+            hints_enabled: true,
+            hide_answer: false,
+            legend: true,
+            daily: false,
+            seed: None,
+            dictionary: None,
+            answers: None,
+            phrases: None,
+            word: None,
+            used_words: HashSet::new(),
+            weights: Vec::new(),
+        }
+    }
+}
+
+impl RustleGameBuilder {
+    /// How many letters the word has, when it comes from the embedded
+    /// dictionary.
+    #[must_use]
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// See [`RustleGame::with_max_tries`].
+    #[must_use]
+    pub fn max_tries(mut self, max_tries: usize) -> Self {
+        self.max_tries = max_tries;
+        self
+    }
+
+    /// See [`RustleGame::with_zen`].
+    #[must_use]
+    pub fn zen(mut self, zen: bool) -> Self {
+        self.zen = zen;
+        self
+    }
+
+    /// See [`RustleGame::with_hard_mode`].
+    #[must_use]
+    pub fn hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    /// See [`RustleGame::with_theme`].
+    #[must_use]
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// See [`RustleGame::with_render_mode`].
+    #[must_use]
+    pub fn render_mode(mut self, render_mode: RenderMode) -> Self {
+        self.render_mode = render_mode;
+        self
+    }
+
+    /// See [`RustleGame::with_numbering`].
+    #[must_use]
+    pub fn numbering(mut self, numbering: Numbering) -> Self {
+        self.numbering = numbering;
+        self
+    }
+
+    /// See [`RustleGame::with_mode`].
+    #[must_use]
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// See [`RustleGame::with_charset`]. It also decides how a word given
+    /// to [`word`](Self::word) is sanitized.
+    #[must_use]
+    pub fn charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self
+    }
+
+    /// See [`RustleGame::with_timer`].
+    #[must_use]
+    pub fn timer(mut self, timed: bool) -> Self {
+        self.timed = timed;
+        self
+    }
+
+    /// See [`RustleGame::with_frequency_hints`].
+    #[must_use]
+    pub fn frequency_hints(mut self, frequency_hints: bool) -> Self {
+        self.frequency_hints = frequency_hints;
+        self
+    }
+
+    /// See [`RustleGame::with_assist`].
+    #[must_use]
+    pub fn assist(mut self, assist: bool) -> Self {
+        self.assist = assist;
+        self
+    }
+
+    /// See [`RustleGame::with_coach`].
+    #[must_use]
+    pub fn coach(mut self, coach: bool) -> Self {
+        self.coach = coach;
+        self
+    }
+
+    /// See [`RustleGame::with_distinct_first`].
+    #[must_use]
+    pub fn distinct_first(mut self, distinct_first: bool) -> Self {
+        self.distinct_first = distinct_first;
+        self
+    }
+
+    /// See [`RustleGame::with_peeks`].
+    #[must_use]
+    pub fn peeks(mut self, peeks: bool) -> Self {
+        self.peeks = peeks;
+        self
+    }
+
+    /// See [`RustleGame::with_allow_any`].
+    #[must_use]
+    pub fn allow_any(mut self, allow_any: bool) -> Self {
+        self.allow_any = allow_any;
+        self
+    }
+
+    /// See [`RustleGame::with_position_hints`].
+    #[must_use]
+    pub fn position_hints(mut self, position_hints: bool) -> Self {
+        self.position_hints = position_hints;
+        self
+    }
+
+    /// See [`RustleGame::with_hints_enabled`].
+    #[must_use]
+    pub fn hints_enabled(mut self, hints_enabled: bool) -> Self {
+        self.hints_enabled = hints_enabled;
+        self
+    }
+
+    /// See [`RustleGame::with_hide_answer`].
+    #[must_use]
+    pub fn hide_answer(mut self, hide_answer: bool) -> Self {
+        self.hide_answer = hide_answer;
+        self
+    }
+
+    /// See [`RustleGame::with_legend`].
+    #[must_use]
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    /// Picks today's word, the same for everyone on a given (UTC) day.
+    #[must_use]
+    pub fn daily(mut self, daily: bool) -> Self {
+        self.daily = daily;
+        self
+    }
+
+    /// Picks the word with an RNG seeded with `seed`, so the same seed and
+    /// word lists always give the same word.
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// The words accepted as guesses, instead of the embedded dictionary.
+    /// They are also the answers unless [`answers`](Self::answers) is set.
+    #[must_use]
+    pub fn dictionary(mut self, dictionary: Vec<String>) -> Self {
+        self.dictionary = Some(dictionary);
+        self
+    }
+
+    /// The words the hidden word is drawn from. They can always be guessed
+    /// too.
+    #[must_use]
+    pub fn answers(mut self, answers: Vec<String>) -> Self {
+        self.answers = Some(answers);
+        self
+    }
+
+    /// Plays with phrases instead of words; see [`RustleGame::from_phrases`].
+    /// The phrases replace any other word list.
+    #[must_use]
+    pub fn phrases(mut self, phrases: Vec<String>) -> Self {
+        self.phrases = Some(phrases);
+        self
+    }
+
+    /// Sets the hidden word instead of picking one. It has to be in the
+    /// dictionary.
+    #[must_use]
+    pub fn word(mut self, word: &str) -> Self {
+        self.word = Some(word.to_string());
+        self
+    }
+
+    /// Words played earlier in the session, like those from
+    /// [`RustleGame::used_words`]. A random word is picked from the other
+    /// answers, until every answer has been played and they start over.
+    #[must_use]
+    pub fn used_words(mut self, used_words: HashSet<String>) -> Self {
+        self.used_words = used_words;
+        self
+    }
+
+    /// How likely each of the [`answers`](Self::answers) is to be picked,
+    /// e.g. how common it is, in the same order. Without a weight for every
+    /// answer, or if they are all zero, every answer is equally likely.
+    #[must_use]
+    pub fn weights(mut self, weights: Vec<f64>) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Starts a game with these options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no word to pick, the word set with
+    /// [`word`](Self::word) isn't in the dictionary, the word picked has
+    /// letters outside the charset, or any of the answers isn't in the
+    /// dictionary or otherwise couldn't be typed in as a guess.
+    ///
+    /// # Panics
+    ///
+    /// With [`daily`](Self::daily), panics if the system clock is set before
+    /// the Unix epoch.
+    pub fn build(&self) -> Result<RustleGame, GameError> {
+        let (answers, valid_guesses) = match &self.phrases {
+            Some(phrases) if phrases.is_empty() => return Err(GameError::NoPhrases),
+            Some(phrases) => (phrases.clone(), phrases.clone()),
+            None => {
+                let valid_guesses = self
+                    .dictionary
+                    .clone()
+                    .unwrap_or_else(|| words_list(self.length));
+                let answers = self
+                    .answers
+                    .clone()
+                    .unwrap_or_else(|| valid_guesses.clone());
+                (answers, valid_guesses)
+            }
+        };
+
+        let mut used_words = self.used_words.clone();
+        let word = if let Some(word) = &self.word {
+            self.checked_word(word, &answers, &valid_guesses)?
+        } else if self.daily {
+            daily_word(&non_empty(answers.clone(), self.length)?, today())
+        } else {
+            let mut rng = match self.seed {
+                Some(seed) => RandomNumberGenerator::seeded(seed),
+                None => RandomNumberGenerator::new(),
+            };
+            random_word(
+                &answers,
+                &self.weights,
+                &mut used_words,
+                self.length,
+                &mut rng,
+            )?
+        };
+
+        let word_length = word.chars().count();
+        let mut game = RustleGame::from_parts(answers, valid_guesses, word, word_length)
+            .with_max_tries(self.max_tries)
+            .with_zen(self.zen)
+            .with_hard_mode(self.hard_mode)
+            .with_theme(self.theme)
+            .with_render_mode(self.render_mode)
+            .with_numbering(self.numbering)
+            .with_mode(self.mode)
+            .with_charset(self.charset)
+            .with_timer(self.timed)
+            .with_frequency_hints(self.frequency_hints)
+            .with_assist(self.assist)
+            .with_coach(self.coach)
+            .with_distinct_first(self.distinct_first)
+            .with_peeks(self.peeks)
+            .with_allow_any(self.allow_any)
+            .with_position_hints(self.position_hints)
+            .with_hints_enabled(self.hints_enabled)
+            .with_hide_answer(self.hide_answer)
+            .with_legend(self.legend);
+        game.phrases = self.phrases.is_some();
+        used_words.insert(game.word.clone());
+        game.used_words = used_words;
+        if self.phrases.is_none() {
+            game.answer_weights.clone_from(&self.weights);
+        }
+        let letters: String = game
+            .word
+            .chars()
+            .filter(|c| !c.is_whitespace() && !game.alphabet.contains(c))
+            .collect();
+        if !letters.is_empty() {
+            return Err(GameError::LettersOutsideAlphabet {
+                word: game.word,
+                letters,
+            });
+        }
+        if !game.can_be_guessed(&game.word) {
+            return Err(GameError::AnswerNotGuessable(game.word));
+        }
+        // Every answer is checked, not only the one picked, so lists that
+        // don't fit together are refused whatever the draw, and later words
+        // picked by `reset_with_new_word` can be typed in too.
+        let valid_guesses: HashSet<&String> = game.valid_guesses.iter().collect();
+        if let Some(word) = game.answers.iter().find(|word| {
+            !valid_guesses.contains(word) || game.parse_guess(word).map_or(true, |g| g != **word)
+        }) {
+            return Err(GameError::AnswerNotGuessable(word.clone()));
+        }
+        Ok(game)
+    }
+
+    /// Cleans up `word` for use as the hidden word, checking that it's in
+    /// the word lists.
+    fn checked_word(
+        &self,
+        word: &str,
+        answers: &[String],
+        valid_guesses: &[String],
+    ) -> Result<String, GameError> {
+        let word = if self.phrases.is_some() {
+            sanitize_phrase(word)
+        } else {
+            self.charset.sanitize(word)
+        };
+        if valid_guesses.contains(&word) || answers.contains(&word) {
+            Ok(word)
+        } else {
+            Err(GameError::UnknownWord(word))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_defaults() {
+        let game = RustleGameBuilder::default().build().unwrap();
+        assert_eq!(game.word_length(), WORD_LENGTH);
+        assert_eq!(game.max_tries(), MAX_TRIES);
+    }
+
+    #[test]
+    fn test_options_are_applied() {
+        let builder = RustleGameBuilder::default()
+            .dictionary(words(&["CRANE", "SLATE", "TRACE", "ADIEU"]))
+            .answers(words(&["ADIEU"]))
+            .max_tries(3)
+            .hard_mode(true);
+        let game = builder.build().unwrap();
+        assert_eq!(game.word(), "ADIEU");
+        assert_eq!(game.max_tries(), 3);
+        assert!(game.is_valid_guess("SLATE"));
+        assert!(game.hard_mode);
+
+        let game = builder.clone().word("slate").build().unwrap();
+        assert_eq!(game.word(), "SLATE");
+        assert!(matches!(
+            builder.word("CRANX").build(),
+            Err(GameError::UnknownWord(_))
+        ));
+    }
+
+    #[test]
+    fn test_answers_have_to_be_guessable() {
+        let result = RustleGameBuilder::default()
+            .dictionary(words(&["CRANE", "SLATE"]))
+            .answers(words(&["crane"]))
+            .build();
+        assert!(matches!(
+            result,
+            Err(GameError::AnswerNotGuessable(word)) if word == "crane"
+        ));
+
+        // Only one of the answers is missing from the dictionary, so every
+        // seed has to find it, not only the ones that draw it.
+        let dictionary = words(&["CRANE", "SLATE", "GRACE", "SLOTH", "BRAVE"]);
+        let answers = words(&["CRANE", "SLATE", "ADIEU", "GRACE", "SLOTH"]);
+        for seed in 0..20 {
+            let result = RustleGameBuilder::default()
+                .dictionary(dictionary.clone())
+                .answers(answers.clone())
+                .seed(seed)
+                .build();
+            assert!(matches!(
+                result,
+                Err(GameError::AnswerNotGuessable(word)) if word == "ADIEU"
+            ));
+        }
+        let game = RustleGameBuilder::default()
+            .dictionary(dictionary)
+            .answers(words(&["CRANE", "SLATE", "GRACE"]))
+            .build()
+            .unwrap();
+        assert!(game.is_valid_guess("BRAVE"));
+    }
+
+    #[test]
+    fn test_answer_letters_must_be_in_the_alphabet() {
+        let builder = RustleGameBuilder::default()
+            .dictionary(words(&["CRANE", "SLATE", "ÉCOLE"]))
+            .answers(words(&["ÉCOLE"]));
+        assert!(matches!(
+            builder.build(),
+            Err(GameError::LettersOutsideAlphabet { word, letters })
+                if word == "ÉCOLE" && letters == "É"
+        ));
+        let game = builder.charset(Charset::Unicode).build().unwrap();
+        assert!(game.alphabet().contains(&'É'));
+    }
+
+    #[test]
+    fn test_seed_is_reproducible() {
+        let builder = RustleGameBuilder::default().seed(42);
+        assert_eq!(builder.build().unwrap().word(), "SPAED");
+        assert_eq!(
+            builder.build().unwrap().word(),
+            builder.build().unwrap().word()
+        );
+    }
+
+    #[test]
+    fn test_used_words_are_not_picked_again() {
+        let answers = words(&["CRANE", "SLATE", "GRACE"]);
+        let builder = RustleGameBuilder::default().answers(answers.clone());
+        let mut used = HashSet::new();
+        for played in 1..=answers.len() {
+            let game = builder.clone().used_words(used).build().unwrap();
+            assert_eq!(game.used_words().len(), played);
+            used = game.used_words().clone();
+        }
+        assert_eq!(used.len(), answers.len());
+        let game = builder.used_words(used).build().unwrap();
+        assert_eq!(game.used_words().len(), 1);
+    }
+
+    #[test]
+    fn test_empty_word_lists() {
+        assert!(matches!(
+            RustleGameBuilder::default().answers(Vec::new()).build(),
+            Err(GameError::EmptyDictionary { word_length: 5 })
+        ));
+        assert!(matches!(
+            RustleGameBuilder::default().phrases(Vec::new()).build(),
+            Err(GameError::NoPhrases)
+        ));
+    }
+}
@@ -0,0 +1,192 @@
+//! Default options read from a config file, so they don't have to be passed
+//! as flags every time.
+
+use crate::stats::config_dir;
+use crate::{RenderMode, Theme};
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+/// The defaults set in `rustle/config.toml`. Options that aren't set there
+/// are left to the flags or the built-in defaults. Flags always win over
+/// the file, and modes that fix a setting, like the daily game's length,
+/// ignore it rather than clash with it.
+///
+/// The file is a small subset of TOML, one `key = value` per line:
+///
+/// ```toml
+/// # Six letters, eight tries.
+/// length = 6
+/// tries = 8
+/// hard = true
+/// theme = "deuteranopia"
+/// render = "symbols"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    pub length: Option<usize>,
+    pub tries: Option<usize>,
+    pub hard_mode: Option<bool>,
+    pub theme: Option<Theme>,
+    pub render_mode: Option<RenderMode>,
+}
+
+impl Config {
+    /// Where the config lives: `rustle/config.toml` in the user's config
+    /// directory, if one can be found.
+    #[must_use]
+    pub fn default_path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("rustle").join("config.toml"))
+    }
+
+    /// Reads the config from `path`. A missing file sets no defaults.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or has a line that isn't
+    /// a known option with a valid value.
+    pub fn load(path: &Path) -> io::Result<Config> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|message| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {message}", path.display()),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Parses the text of a config file.
+    ///
+    /// # Errors
+    ///
+    /// Returns what is wrong with the first bad line, with its line number.
+    pub fn parse(text: &str) -> Result<Config, String> {
+        let mut config = Config::default();
+        for (index, line) in text.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            config
+                .set(line)
+                .map_err(|message| format!("line {}: {message}", index + 1))?;
+        }
+        Ok(config)
+    }
+
+    fn set(&mut self, line: &str) -> Result<(), String> {
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("expected `key = value`, found `{line}`"))?;
+        let (key, value) = (key.trim(), value.trim());
+        match key {
+            "length" => self.length = Some(parse_positive(key, value)?),
+            "tries" => self.tries = Some(parse_positive(key, value)?),
+            "hard" => {
+                self.hard_mode = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("hard expects true or false, found {value}"))?,
+                );
+            }
+            "theme" => {
+                let name = parse_string(key, value)?;
+                self.theme = Some(Theme::from_name(name).ok_or_else(|| {
+                    format!(
+                        "unknown theme {name}, expected one of {}",
+                        Theme::NAMES.join(", ")
+                    )
+                })?);
+            }
+            "render" => {
+                let name = parse_string(key, value)?;
+                self.render_mode = Some(RenderMode::from_name(name).ok_or_else(|| {
+                    format!(
+                        "unknown render mode {name}, expected one of {}",
+                        RenderMode::NAMES.join(", ")
+                    )
+                })?);
+            }
+            _ => return Err(format!("unknown option {key}")),
+        }
+        Ok(())
+    }
+}
+
+/// `line` without a trailing `# comment`. A `#` inside a quoted string is
+/// kept.
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_positive(key: &str, value: &str) -> Result<usize, String> {
+    match value.parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(format!("{key} expects a positive number, found {value}")),
+    }
+}
+
+fn parse_string<'a>(key: &str, value: &'a str) -> Result<&'a str, String> {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .ok_or_else(|| format!("{key} expects a quoted string, found {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = "\
+# My defaults
+length = 6
+tries=8   # a few more
+hard = true
+
+theme = \"deuteranopia\"
+render = \"symbols\"
+";
+        assert_eq!(
+            Config::parse(text),
+            Ok(Config {
+                length: Some(6),
+                tries: Some(8),
+                hard_mode: Some(true),
+                theme: Some(Theme::DEUTERANOPIA),
+                render_mode: Some(RenderMode::Symbols),
+            })
+        );
+        assert_eq!(Config::parse(""), Ok(Config::default()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Config::parse("length = 6\ncolour = \"red\""),
+            Err("line 2: unknown option colour".to_string())
+        );
+        assert!(Config::parse("length = 0").is_err());
+        assert!(Config::parse("hard = yes").is_err());
+        assert!(Config::parse("theme = classic").is_err());
+        assert!(Config::parse("theme = \"sepia\"").is_err());
+        assert!(Config::parse("just words").is_err());
+    }
+
+    #[test]
+    fn test_missing_file_sets_no_defaults() {
+        let path = std::env::temp_dir().join("rustle-no-such-config.toml");
+        assert_eq!(Config::load(&path).unwrap(), Config::default());
+    }
+}
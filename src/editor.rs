@@ -0,0 +1,356 @@
+//! A small line editor for the guess prompt: arrow keys move the cursor,
+//! backspace works anywhere in the line, and up and down recall earlier
+//! lines. When stdin or stdout isn't a terminal it falls back to plain
+//! buffered reads.
+//!
+//! This is all a guess prompt needs, so it isn't worth a line-editing crate
+//! like `rustyline`, which brings a dozen more dependencies and doesn't
+//! build for the `wasm` feature's target.
+
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+
+/// A key press, decoded from the bytes a terminal sends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    /// Ctrl-U: clears the line.
+    Clear,
+    /// Ctrl-D: end of input on an empty line, delete otherwise.
+    EndOfFile,
+    /// The input itself has ended, e.g. because the terminal hung up.
+    Closed,
+    /// Ctrl-C.
+    Interrupt,
+    /// Anything else, which is ignored.
+    Other,
+}
+
+/// What the caller should do after a key has been handled.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Continue,
+    Done(String),
+    EndOfFile,
+    Interrupted,
+}
+
+/// Reads lines from the terminal, remembering the ones entered so far.
+#[derive(Debug, Default)]
+pub(crate) struct LineEditor {
+    history: Vec<String>,
+}
+
+impl LineEditor {
+    /// Reads a line into `buf`, like [`io::Stdin::read_line`]: the line
+    /// ends with a newline, and `Ok(0)` means stdin was closed.
+    ///
+    /// Ctrl-C gives an error of kind [`io::ErrorKind::Interrupted`], since the
+    /// terminal doesn't send a signal while it is being edited.
+    pub(crate) fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+            return io::stdin().lock().read_line(buf);
+        }
+        let Some(_raw) = RawMode::enable() else {
+            return io::stdin().lock().read_line(buf);
+        };
+
+        let mut state = LineState::new(&self.history);
+        let mut stdin = io::stdin().lock();
+        let mut stdout = io::stdout().lock();
+        loop {
+            let key = read_key(&mut stdin)?;
+            match state.handle(key) {
+                Outcome::Continue => state.redraw(&mut stdout)?,
+                Outcome::Done(line) => {
+                    write!(stdout, "\r\n")?;
+                    stdout.flush()?;
+                    if !line.trim().is_empty() && self.history.last() != Some(&line) {
+                        self.history.push(line.clone());
+                    }
+                    buf.push_str(&line);
+                    buf.push('\n');
+                    return Ok(line.len() + 1);
+                }
+                Outcome::EndOfFile => {
+                    write!(stdout, "\r\n")?;
+                    stdout.flush()?;
+                    return Ok(0);
+                }
+                Outcome::Interrupted => {
+                    write!(stdout, "\r\n")?;
+                    stdout.flush()?;
+                    return Err(io::ErrorKind::Interrupted.into());
+                }
+            }
+        }
+    }
+}
+
+/// The line being edited.
+struct LineState<'a> {
+    history: &'a [String],
+    chars: Vec<char>,
+    cursor: usize,
+    /// Which history entry is shown, or `history.len()` for the new line.
+    recalled: usize,
+    /// The new line, kept while browsing the history.
+    draft: Vec<char>,
+}
+
+impl<'a> LineState<'a> {
+    fn new(history: &'a [String]) -> Self {
+        Self {
+            history,
+            chars: Vec::new(),
+            cursor: 0,
+            recalled: history.len(),
+            draft: Vec::new(),
+        }
+    }
+
+    fn handle(&mut self, key: Key) -> Outcome {
+        match key {
+            Key::Char(c) => {
+                self.chars.insert(self.cursor, c);
+                self.cursor += 1;
+            }
+            Key::Enter => return Outcome::Done(self.chars.iter().collect()),
+            Key::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.chars.remove(self.cursor);
+            }
+            Key::EndOfFile if self.chars.is_empty() => return Outcome::EndOfFile,
+            Key::Closed => return Outcome::EndOfFile,
+            Key::Delete | Key::EndOfFile if self.cursor < self.chars.len() => {
+                self.chars.remove(self.cursor);
+            }
+            Key::Left => self.cursor = self.cursor.saturating_sub(1),
+            Key::Right => self.cursor = (self.cursor + 1).min(self.chars.len()),
+            Key::Home => self.cursor = 0,
+            Key::End => self.cursor = self.chars.len(),
+            Key::Clear => {
+                self.chars.clear();
+                self.cursor = 0;
+            }
+            Key::Up if self.recalled > 0 => self.recall(self.recalled - 1),
+            Key::Down if self.recalled < self.history.len() => self.recall(self.recalled + 1),
+            Key::Interrupt => return Outcome::Interrupted,
+            _ => {}
+        }
+        Outcome::Continue
+    }
+
+    /// Shows history entry `index`, or the draft past the end of the history.
+    fn recall(&mut self, index: usize) {
+        if self.recalled == self.history.len() {
+            self.draft.clone_from(&self.chars);
+        }
+        self.recalled = index;
+        self.chars = match self.history.get(index) {
+            Some(line) => line.chars().collect(),
+            None => self.draft.clone(),
+        };
+        self.cursor = self.chars.len();
+    }
+
+    fn redraw(&self, out: &mut impl Write) -> io::Result<()> {
+        let line: String = self.chars.iter().collect();
+        write!(out, "\r\x1b[K{line}")?;
+        let back = self.chars.len() - self.cursor;
+        if back > 0 {
+            write!(out, "\x1b[{back}D")?;
+        }
+        out.flush()
+    }
+}
+
+fn read_byte(input: &mut impl Read) -> io::Result<Option<u8>> {
+    let mut byte = [0];
+    match input.read(&mut byte)? {
+        0 => Ok(None),
+        _ => Ok(Some(byte[0])),
+    }
+}
+
+/// Reads one key press.
+fn read_key(input: &mut impl Read) -> io::Result<Key> {
+    let Some(byte) = read_byte(input)? else {
+        return Ok(Key::Closed);
+    };
+    Ok(match byte {
+        b'\r' | b'\n' => Key::Enter,
+        0x7f | 0x08 => Key::Backspace,
+        0x01 => Key::Home,
+        0x03 => Key::Interrupt,
+        0x04 => Key::EndOfFile,
+        0x05 => Key::End,
+        0x15 => Key::Clear,
+        0x1b => read_escape(input)?,
+        byte if byte < 0x20 => Key::Other,
+        byte => read_char(input, byte)?,
+    })
+}
+
+/// Decodes the rest of an escape sequence such as `ESC [ A`.
+fn read_escape(input: &mut impl Read) -> io::Result<Key> {
+    if !matches!(read_byte(input)?, Some(b'[' | b'O')) {
+        return Ok(Key::Other);
+    }
+    Ok(match read_byte(input)? {
+        Some(b'A') => Key::Up,
+        Some(b'B') => Key::Down,
+        Some(b'C') => Key::Right,
+        Some(b'D') => Key::Left,
+        Some(b'H') => Key::Home,
+        Some(b'F') => Key::End,
+        Some(b'3') if read_byte(input)? == Some(b'~') => Key::Delete,
+        _ => Key::Other,
+    })
+}
+
+/// Decodes a UTF-8 character whose first byte is `first`.
+fn read_char(input: &mut impl Read, first: u8) -> io::Result<Key> {
+    let len = match first {
+        0xf0.. => 4,
+        0xe0.. => 3,
+        0xc0.. => 2,
+        _ => 1,
+    };
+    let mut bytes = vec![first];
+    for _ in 1..len {
+        match read_byte(input)? {
+            Some(byte) => bytes.push(byte),
+            None => break,
+        }
+    }
+    Ok(std::str::from_utf8(&bytes)
+        .ok()
+        .and_then(|s| s.chars().next())
+        .map_or(Key::Other, Key::Char))
+}
+
+/// Turns off line buffering and echo while it is alive, so keys can be
+/// handled one at a time.
+#[cfg(unix)]
+struct RawMode {
+    original: libc::termios,
+}
+
+#[cfg(unix)]
+impl RawMode {
+    fn enable() -> Option<Self> {
+        // SAFETY: termios is plain data that tcgetattr fills in, and stdin
+        // stays open for the duration of both calls.
+        unsafe {
+            let mut original: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &raw mut original) != 0 {
+                return None;
+            }
+            let mut settings = original;
+            settings.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG | libc::IEXTEN);
+            settings.c_iflag &= !(libc::IXON | libc::ICRNL);
+            settings.c_cc[libc::VMIN] = 1;
+            settings.c_cc[libc::VTIME] = 0;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw const settings) != 0 {
+                return None;
+            }
+            Some(Self { original })
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // SAFETY: restores the settings read in `enable`.
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw const self.original);
+        }
+    }
+}
+
+/// Raw mode isn't supported here, so lines are always read plainly.
+#[cfg(not(unix))]
+struct RawMode;
+
+#[cfg(not(unix))]
+impl RawMode {
+    fn enable() -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feeds `input` to a fresh line, returning how it ended.
+    fn type_keys(history: &[String], mut input: &[u8]) -> Outcome {
+        let mut state = LineState::new(history);
+        loop {
+            match state.handle(read_key(&mut input).unwrap()) {
+                Outcome::Continue => {}
+                outcome => return outcome,
+            }
+        }
+    }
+
+    #[test]
+    fn test_editing() {
+        // "crnae", then fix the swapped letters with the arrow keys.
+        let keys = b"crnae\x1b[D\x1b[D\x7f\x1b[Cn\r";
+        assert_eq!(type_keys(&[], keys), Outcome::Done("crane".to_string()));
+        assert_eq!(
+            type_keys(&[], b"crane!\x1b[D\x1b[3~\r"),
+            Outcome::Done("crane".to_string())
+        );
+        assert_eq!(
+            type_keys(&[], b"slat\x01x\x05e\r"),
+            Outcome::Done("xslate".to_string())
+        );
+        assert_eq!(
+            type_keys(&[], b"oops\x15crane\r"),
+            Outcome::Done("crane".to_string())
+        );
+    }
+
+    #[test]
+    fn test_history() {
+        let history = vec!["crane".to_string(), "slate".to_string()];
+        assert_eq!(
+            type_keys(&history, b"\x1b[A\r"),
+            Outcome::Done("slate".to_string())
+        );
+        assert_eq!(
+            type_keys(&history, b"\x1b[A\x1b[A\x1b[A\r"),
+            Outcome::Done("crane".to_string())
+        );
+        assert_eq!(
+            type_keys(&history, b"br\x1b[A\x1b[B\r"),
+            Outcome::Done("br".to_string())
+        );
+    }
+
+    #[test]
+    fn test_end_of_input_and_interrupt() {
+        assert_eq!(type_keys(&[], b"\x04"), Outcome::EndOfFile);
+        assert_eq!(type_keys(&[], b""), Outcome::EndOfFile);
+        // Once the input is gone, text on the line doesn't keep it going.
+        assert_eq!(type_keys(&[], b"ab"), Outcome::EndOfFile);
+        assert_eq!(type_keys(&[], b"ab\x1b[D"), Outcome::EndOfFile);
+        assert_eq!(type_keys(&[], b"ab\x03"), Outcome::Interrupted);
+        assert_eq!(
+            type_keys(&[], "pingüino\r".as_bytes()),
+            Outcome::Done("pingüino".to_string())
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use std::{error, fmt, io};
+
+/// Why a game couldn't be set up.
+#[derive(Debug)]
+pub enum GameError {
+    /// There are no words of the requested length to pick from.
+    EmptyDictionary { word_length: usize },
+    /// A phrase list has no phrases in it.
+    NoPhrases,
+    /// The word asked for as the answer isn't in the dictionary.
+    UnknownWord(String),
+    /// Strict loading found lines in a word list that aren't
+    /// `word_length`-letter words; each comes with its line number.
+    RejectedLines {
+        word_length: usize,
+        lines: Vec<(usize, String)>,
+    },
+    /// The word picked from the answers has letters outside the alphabet
+    /// the game can be played with, like an accented word in a game of
+    /// English letters; `letters` lists them.
+    LettersOutsideAlphabet { word: String, letters: String },
+    /// One of the answers couldn't be typed in as a guess, because it isn't
+    /// in the dictionary or has letters outside the charset.
+    AnswerNotGuessable(String),
+    /// A word list couldn't be read.
+    IoError(io::Error),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::EmptyDictionary { word_length } => {
+                write!(f, "the dictionary has no {word_length}-letter words")
+            }
+            GameError::NoPhrases => write!(f, "the phrase list is empty"),
+            GameError::UnknownWord(word) => write!(f, "{word} isn't in the dictionary"),
+            GameError::RejectedLines { word_length, lines } => {
+                write!(
+                    f,
+                    "the word list has lines that aren't {word_length}-letter words:"
+                )?;
+                for (number, line) in lines {
+                    write!(f, "\n  line {number}: {line}")?;
+                }
+                Ok(())
+            }
+            GameError::LettersOutsideAlphabet { word, letters } => {
+                write!(
+                    f,
+                    "the answer {word} has letters outside the alphabet: {letters}"
+                )
+            }
+            GameError::AnswerNotGuessable(word) => {
+                write!(f, "the answer {word} can't be entered as a guess")
+            }
+            GameError::IoError(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl error::Error for GameError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            GameError::IoError(e) => Some(e),
+            GameError::EmptyDictionary { .. }
+            | GameError::NoPhrases
+            | GameError::UnknownWord(_)
+            | GameError::LettersOutsideAlphabet { .. }
+            | GameError::AnswerNotGuessable(_)
+            | GameError::RejectedLines { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for GameError {
+    fn from(e: io::Error) -> Self {
+        GameError::IoError(e)
+    }
+}
+
+/// Why a guess was turned down. Nothing is recorded for a rejected guess.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidGuess {
+    /// The guess has characters other than letters, like digits or
+    /// punctuation.
+    NotLetters,
+    /// The guess doesn't have as many letters as the hidden word.
+    WrongLength { expected: usize },
+    /// The guess doesn't have its spaces where the hidden phrase does.
+    WrongShape { shape: String },
+    /// The guess isn't in any of the game's word lists.
+    NotInDictionary(String),
+    /// In hard mode, the guess ignores a revealed hint; the message says
+    /// which one.
+    HardMode(String),
+    /// With distinct first letters required, the opening guess repeats a
+    /// letter.
+    RepeatedLetters,
+}
+
+impl fmt::Display for InvalidGuess {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidGuess::NotLetters => write!(f, "Guesses must be letters only."),
+            InvalidGuess::WrongLength { expected } => {
+                write!(f, "Your guess must be {expected} letters.")
+            }
+            InvalidGuess::WrongShape { shape } => {
+                write!(f, "Your guess must be shaped like {shape}.")
+            }
+            InvalidGuess::NotInDictionary(guess) => {
+                write!(f, "{guess} isn't in the Rustle dictionary.")
+            }
+            InvalidGuess::HardMode(violation) => write!(f, "{violation}"),
+            InvalidGuess::RepeatedLetters => {
+                write!(f, "Your first guess can't repeat a letter.")
+            }
+        }
+    }
+}
+
+impl error::Error for InvalidGuess {}
@@ -0,0 +1,157 @@
+//! The values the flags accept, listed with what they do, so they can be
+//! found without reading the docs.
+
+use crate::{Difficulty, Mode, Numbering, RenderMode};
+use std::fmt::Write;
+
+const THEMES: [(&str, &str); 4] = [
+    ("classic", "Green, yellow and red (the default)"),
+    (
+        "classic-gray",
+        "Green and yellow, with absent letters dimmed gray instead of red",
+    ),
+    (
+        "high-contrast",
+        "White, cyan and gray, which stand far apart on any background",
+    ),
+    (
+        "deuteranopia",
+        "Blue, yellow and gray, for red-green color blindness",
+    ),
+];
+
+fn describe_render_mode(mode: RenderMode) -> &'static str {
+    match mode {
+        RenderMode::Color => "Colored letters (the default in a terminal)",
+        RenderMode::Symbols => "[A] for correct, (A) for misplaced and plain A for absent letters",
+        RenderMode::Describe => "Every guess described in a sentence, for screen readers",
+    }
+}
+
+fn describe_mode(mode: Mode) -> &'static str {
+    match mode {
+        Mode::Letters => "Every letter is marked correct, misplaced or absent (the default)",
+        Mode::Distance => "Every guess is only told how many edits away from the word it is",
+    }
+}
+
+fn describe_difficulty(difficulty: Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Easy => "Only the most common quarter of the words",
+        Difficulty::Medium => "The most common half of the words",
+        Difficulty::Hard => "Every word, however rare (the default)",
+    }
+}
+
+fn describe_numbering(numbering: Numbering) -> &'static str {
+    match numbering {
+        Numbering::FromOne => "Rows counted 1:, 2:, ... (the default)",
+        Numbering::FromZero => "Rows counted 0:, 1:, ...",
+        Numbering::OfTotal => "Rows counted Guess 1/6:, Guess 2/6:, ...",
+    }
+}
+
+/// The color themes, for `--list-themes`.
+#[must_use]
+pub fn themes() -> String {
+    let mut out = String::new();
+    write_section(&mut out, "Themes (--theme NAME)", &THEMES);
+    out
+}
+
+/// The ways a game can be shown and played, for `--list-modes`.
+#[must_use]
+pub fn modes() -> String {
+    let mut out = String::new();
+    write_section(
+        &mut out,
+        "Render modes (--symbols, --describe, or render = \"NAME\" in the config)",
+        &RenderMode::ALL.map(|mode| (mode.name(), describe_render_mode(mode))),
+    );
+    out.push('\n');
+    write_section(
+        &mut out,
+        "Game modes (--mode NAME)",
+        &Mode::ALL.map(|mode| (mode.name(), describe_mode(mode))),
+    );
+    out.push('\n');
+    write_section(
+        &mut out,
+        "Difficulties (--difficulty NAME)",
+        &Difficulty::ALL.map(|difficulty| (difficulty.name(), describe_difficulty(difficulty))),
+    );
+    out.push('\n');
+    write_section(
+        &mut out,
+        "Row numbering (--numbering NAME)",
+        &Numbering::ALL.map(|numbering| (numbering.name(), describe_numbering(numbering))),
+    );
+    out
+}
+
+fn write_section(out: &mut String, title: &str, entries: &[(&str, &str)]) {
+    let width = entries
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let _ = writeln!(out, "{title}:");
+    for (name, description) in entries {
+        let _ = writeln!(out, "  {name:<width$}  {description}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Theme;
+
+    #[test]
+    fn test_every_value_is_listed() {
+        let names: Vec<&str> = THEMES.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, Theme::NAMES);
+        let themes = themes();
+        for name in Theme::NAMES {
+            assert!(themes.contains(&format!("  {name} ")), "{name}");
+        }
+
+        // Each match fails to compile once a variant is added, until it is
+        // added to `ALL` as well.
+        let render_mode = |mode| match mode {
+            RenderMode::Color => 0,
+            RenderMode::Symbols => 1,
+            RenderMode::Describe => 2,
+        };
+        let mode = |mode| match mode {
+            Mode::Letters => 0,
+            Mode::Distance => 1,
+        };
+        let difficulty = |difficulty| match difficulty {
+            Difficulty::Easy => 0,
+            Difficulty::Medium => 1,
+            Difficulty::Hard => 2,
+        };
+        let numbering = |numbering| match numbering {
+            Numbering::FromOne => 0,
+            Numbering::FromZero => 1,
+            Numbering::OfTotal => 2,
+        };
+        let mut indices = Vec::new();
+        indices.extend(RenderMode::ALL.map(render_mode));
+        indices.extend(Mode::ALL.map(mode));
+        indices.extend(Difficulty::ALL.map(difficulty));
+        indices.extend(Numbering::ALL.map(numbering));
+        assert_eq!(indices, [0, 1, 2, 0, 1, 0, 1, 2, 0, 1, 2]);
+
+        let modes = modes();
+        let names = RenderMode::ALL
+            .map(RenderMode::name)
+            .into_iter()
+            .chain(Mode::ALL.map(Mode::name))
+            .chain(Difficulty::ALL.map(Difficulty::name))
+            .chain(Numbering::ALL.map(Numbering::name));
+        for name in names {
+            assert!(modes.contains(&format!("  {name} ")), "{name}");
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Points for won games, added up per player between runs.
+
+use crate::json::{self, Value};
+use crate::stats::config_dir;
+use crate::MAX_TRIES;
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Points for any win.
+const WIN_POINTS: u32 = 100;
+/// Extra points for every guess fewer than [`MAX_TRIES`] that a win took.
+const POINTS_PER_SPARE_GUESS: u32 = 50;
+/// Wins faster than this earn a time bonus, one point per three seconds to
+/// spare.
+const TIME_BONUS_SECONDS: u64 = 300;
+
+/// How many entries are shown after a game.
+pub const SHOWN_ENTRIES: usize = 5;
+
+/// The points for a game won in `tries` guesses and `elapsed` time. Fewer
+/// guesses and faster wins score more, and hard mode earns half as much
+/// again.
+#[must_use]
+pub fn score_game(tries: usize, elapsed: Duration, hard_mode: bool) -> u32 {
+    let spare_guesses = u32::try_from(MAX_TRIES.saturating_sub(tries)).unwrap_or(0);
+    let time_bonus = TIME_BONUS_SECONDS.saturating_sub(elapsed.as_secs()) / 3;
+    let points = WIN_POINTS
+        + POINTS_PER_SPARE_GUESS * spare_guesses
+        + u32::try_from(time_bonus).unwrap_or(0);
+    if hard_mode {
+        points * 3 / 2
+    } else {
+        points
+    }
+}
+
+/// One player's total.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub name: String,
+    pub points: u64,
+    pub wins: u32,
+}
+
+/// Every player's points, highest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Leaderboard {
+    entries: Vec<Entry>,
+}
+
+impl Leaderboard {
+    /// Adds a win worth `points` to `name`'s total.
+    pub fn record(&mut self, name: &str, points: u32) {
+        match self.entries.iter_mut().find(|entry| entry.name == name) {
+            Some(entry) => {
+                entry.points += u64::from(points);
+                entry.wins += 1;
+            }
+            None => self.entries.push(Entry {
+                name: name.to_string(),
+                points: u64::from(points),
+                wins: 1,
+            }),
+        }
+        self.entries
+            .sort_by_key(|entry| std::cmp::Reverse(entry.points));
+    }
+
+    /// The `n` players with the most points.
+    #[must_use]
+    pub fn top(&self, n: usize) -> &[Entry] {
+        &self.entries[..n.min(self.entries.len())]
+    }
+
+    /// Where the leaderboard lives: `rustle/leaderboard.json` in the user's
+    /// config directory, if one can be found.
+    #[must_use]
+    pub fn default_path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("rustle").join("leaderboard.json"))
+    }
+
+    /// Reads the leaderboard from `path`. A missing file means nobody has
+    /// scored yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't a valid
+    /// leaderboard.
+    pub fn load(path: &Path) -> io::Result<Leaderboard> {
+        Ok(json::load_file(path, "a valid leaderboard", Self::from_json)?.unwrap_or_default())
+    }
+
+    /// Writes the leaderboard to `path`, creating its directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file or its directory can't be written.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        json::save_file(path, &self.to_json())
+    }
+
+    fn to_json(&self) -> Value {
+        let entries: Vec<Value> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                json::object([
+                    ("name", entry.name.as_str().into()),
+                    ("points", entry.points.into()),
+                    ("wins", u64::from(entry.wins).into()),
+                ])
+            })
+            .collect();
+        json::object([("entries", entries.into())])
+    }
+
+    fn from_json(text: &str) -> Option<Leaderboard> {
+        let value = json::parse(text)?;
+        let mut entries = value
+            .get("entries")?
+            .as_array()?
+            .iter()
+            .map(|entry| {
+                Some(Entry {
+                    name: entry.get("name")?.as_str()?.to_string(),
+                    points: entry.get("points")?.as_u64()?,
+                    wins: u32::try_from(entry.get("wins")?.as_u64()?).ok()?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.points));
+        Some(Leaderboard { entries })
+    }
+
+    /// The top `n` players as a numbered table.
+    #[must_use]
+    pub fn render(&self, n: usize) -> String {
+        let mut out = "Leaderboard:\n".to_string();
+        for (i, entry) in self.top(n).iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "{}. {} {} points ({} wins)",
+                i + 1,
+                entry.name,
+                entry.points,
+                entry.wins
+            );
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_game() {
+        let minute = Duration::from_mins(1);
+        assert_eq!(score_game(1, minute, false), 100 + 250 + 80);
+        assert_eq!(score_game(6, minute, false), 100 + 80);
+        assert_eq!(score_game(3, Duration::ZERO, false), 100 + 150 + 100);
+        assert_eq!(score_game(6, Duration::from_mins(10), false), 100);
+        assert_eq!(score_game(6, Duration::from_mins(10), true), 150);
+        assert_eq!(score_game(8, Duration::from_mins(10), false), 100);
+        assert!(score_game(2, minute, false) > score_game(3, minute, false));
+        assert!(score_game(3, minute, false) > score_game(3, minute * 2, false));
+    }
+
+    #[test]
+    fn test_record_and_rank() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.record("ada", 200);
+        leaderboard.record("bob", 300);
+        leaderboard.record("ada", 150);
+        let top = leaderboard.top(1);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].name, "ada");
+        assert_eq!(top[0].points, 350);
+        assert_eq!(top[0].wins, 2);
+        assert_eq!(leaderboard.top(10).len(), 2);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.record("ada \"the\" player", 200);
+        leaderboard.record("bob", 300);
+        let text = leaderboard.to_json().to_string();
+        assert_eq!(Leaderboard::from_json(&text), Some(leaderboard));
+        assert_eq!(Leaderboard::from_json("{}"), None);
+    }
+}
@@ -0,0 +1,3830 @@
+#![warn(clippy::all, clippy::pedantic)]
+
+pub mod boards;
+mod builder;
+mod config;
+mod editor;
+mod error;
+pub mod help;
+mod json;
+pub mod leaderboard;
+pub mod openers;
+pub mod render;
+pub mod saved;
+pub mod solver;
+pub mod stats;
+pub mod theme;
+pub mod tournament;
+pub mod transcript;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use builder::RustleGameBuilder;
+pub use config::Config;
+pub use error::{GameError, InvalidGuess};
+pub use leaderboard::{score_game, Leaderboard};
+pub use render::{
+    describe_guess, emoji_row, json_error, json_guess, json_loss, json_result, render_distance_row,
+    render_empty_row, render_guess, render_keyboard, render_legend, render_row, row_prefix,
+    terminal_width, visible_width, Numbering, RenderMode, CELL_WIDTH, EMPTY_CELL,
+};
+pub use stats::Stats;
+pub use theme::Theme;
+
+use bracket_random::prelude::RandomNumberGenerator;
+use colored::{Color, ColoredString, Colorize};
+use editor::LineEditor;
+use saved::SavedGame;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write as _;
+use std::hash::BuildHasher;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{fs, io};
+
+const ALL_WORDS: &str = include_str!("words.txt");
+
+pub const WORD_LENGTH: usize = 5;
+pub const MAX_TRIES: usize = 6;
+
+pub const REVEAL_COMMAND: &str = "/reveal";
+pub const QUIT_COMMAND: &str = "/quit";
+pub const UNDO_COMMAND: &str = "/undo";
+pub const HINT_COMMAND: &str = "/hint";
+pub const REPLAY_COMMAND: &str = "/replay";
+pub const FREQ_COMMAND: &str = "/freq";
+pub const PEEK_COMMAND: &str = "/peek";
+pub const POSITIONS_COMMAND: &str = "/positions";
+pub const MULLIGAN_COMMAND: &str = "/mulligan";
+
+/// How many of the likeliest letters `/positions` shows for each position.
+pub const SHOWN_POSITION_LETTERS: usize = 3;
+
+/// Stands in for the letters that a peek doesn't reveal.
+pub const PEEK_PLACEHOLDER: char = '_';
+/// How many times a game lets the player peek.
+pub const MAX_PEEKS: usize = 2;
+/// Words further than this from a rejected guess aren't suggested instead.
+pub const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Returns every word of the embedded dictionary, sanitized, deduplicated
+/// and filtered to `word_length` letters.
+#[must_use]
+pub fn words_list(word_length: usize) -> Vec<String> {
+    filter_words(skip_header(ALL_WORDS.lines()), word_length, Charset::Ascii)
+}
+
+/// Skips the title lines at the top of a word list: everything before the
+/// first line that is a plain word. A list without a title loses nothing,
+/// and neither does the embedded list if its title changes.
+fn skip_header<'a>(lines: impl Iterator<Item = &'a str>) -> impl Iterator<Item = &'a str> {
+    lines.skip_while(|line| {
+        let (word, _) = split_frequency(line);
+        word.is_empty() || !word.chars().all(char::is_alphabetic)
+    })
+}
+
+/// Splits the optional frequency column off a word list line, e.g.
+/// `crane 1520` or `crane,1520`.
+fn split_frequency(line: &str) -> (&str, Option<u64>) {
+    let line = line.trim();
+    line.rsplit_once(|c: char| c.is_whitespace() || c == ',')
+        .and_then(|(word, frequency)| Some((word.trim(), Some(frequency.parse().ok()?))))
+        .unwrap_or((line, None))
+}
+
+/// Reads a dictionary file with one word per line, keeping the words that
+/// are `word_length` letters long once sanitized for `charset`.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read or has no words of that length.
+pub fn load_dictionary_file(
+    path: impl AsRef<Path>,
+    word_length: usize,
+    charset: Charset,
+) -> Result<Vec<String>, GameError> {
+    load_dictionary_entries(path, word_length, charset).map(into_words)
+}
+
+/// Like [`load_dictionary_file`], but keeps the frequency column of lines
+/// such as `crane 1520`.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read or has no words of that length.
+pub fn load_dictionary_entries(
+    path: impl AsRef<Path>,
+    word_length: usize,
+    charset: Charset,
+) -> Result<Vec<DictionaryEntry>, GameError> {
+    parse_dictionary_entries(&read_word_list(path)?, word_length, charset)
+}
+
+/// Like [`load_dictionary_entries`], but every line has to be a
+/// `word_length`-letter word, optionally followed by a frequency, instead of
+/// the others being skipped. See [`check_dictionary_entries`].
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read or has lines that aren't
+/// words of that length.
+pub fn load_dictionary_entries_strict(
+    path: impl AsRef<Path>,
+    word_length: usize,
+    charset: Charset,
+) -> Result<Vec<DictionaryEntry>, GameError> {
+    check_dictionary_entries(&read_word_list(path)?, word_length, charset)
+        .map_err(|lines| GameError::RejectedLines { word_length, lines })
+}
+
+fn read_word_list(path: impl AsRef<Path>) -> io::Result<String> {
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("couldn't read dictionary {}: {e}", path.display()),
+        )
+    })
+}
+
+/// Parses a word list with one word of `len` English letters per line,
+/// rejecting it if any line is something else instead of skipping that
+/// line, so the list can be fixed. Blank lines are allowed.
+///
+/// # Errors
+///
+/// Returns every rejected line with its line number, counting from 1.
+pub fn load_words_strict(src: &str, len: usize) -> Result<Vec<String>, Vec<(usize, String)>> {
+    check_dictionary_entries(src, len, Charset::Ascii).map(into_words)
+}
+
+/// [`load_words_strict`] for any `charset`, keeping the frequency column of
+/// lines such as `crane 1520`.
+///
+/// # Errors
+///
+/// Returns every rejected line with its line number, counting from 1.
+pub fn check_dictionary_entries(
+    text: &str,
+    word_length: usize,
+    charset: Charset,
+) -> Result<Vec<DictionaryEntry>, Vec<(usize, String)>> {
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+    let mut rejected = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let (word, frequency) = split_frequency(line);
+        if word.is_empty() {
+            continue;
+        }
+        let sanitized = charset.sanitize(word);
+        if !word.chars().all(|c| charset.is_letter(c)) || sanitized.chars().count() != word_length {
+            rejected.push((index + 1, line.trim().to_string()));
+        } else if seen.insert(sanitized.clone()) {
+            entries.push(DictionaryEntry {
+                word: sanitized,
+                frequency,
+            });
+        }
+    }
+    if rejected.is_empty() {
+        Ok(entries)
+    } else {
+        Err(rejected)
+    }
+}
+
+/// Parses a dictionary that is already in memory, one word per line,
+/// keeping each `word_length`-letter word once. Unlike the other loaders it
+/// does no IO, so it also works where there is no file system.
+///
+/// # Errors
+///
+/// Returns an error if there are no words of that length.
+pub fn parse_dictionary(
+    text: &str,
+    word_length: usize,
+    charset: Charset,
+) -> Result<Vec<String>, GameError> {
+    parse_dictionary_entries(text, word_length, charset).map(into_words)
+}
+
+fn parse_dictionary_entries(
+    text: &str,
+    word_length: usize,
+    charset: Charset,
+) -> Result<Vec<DictionaryEntry>, GameError> {
+    non_empty(
+        filter_entries(text.lines(), word_length, charset),
+        word_length,
+    )
+}
+
+/// Reads a dictionary with one word per line from `reader` until it is
+/// exhausted, keeping each `word_length`-letter word once.
+///
+/// # Errors
+///
+/// Returns an error if reading fails or there are no words of that length.
+pub fn read_dictionary(
+    reader: impl BufRead,
+    word_length: usize,
+    charset: Charset,
+) -> Result<Vec<String>, GameError> {
+    read_dictionary_entries(reader, word_length, charset).map(into_words)
+}
+
+/// Like [`read_dictionary`], but keeps the frequency column of lines such as
+/// `crane 1520`.
+///
+/// # Errors
+///
+/// Returns an error if reading fails or there are no words of that length.
+pub fn read_dictionary_entries(
+    reader: impl BufRead,
+    word_length: usize,
+    charset: Charset,
+) -> Result<Vec<DictionaryEntry>, GameError> {
+    let lines = reader.lines().collect::<Result<Vec<_>, _>>()?;
+    non_empty(
+        filter_entries(lines.iter().map(String::as_str), word_length, charset),
+        word_length,
+    )
+}
+
+/// A word from a word list, with how common it is if the list says.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryEntry {
+    pub word: String,
+    pub frequency: Option<u64>,
+}
+
+fn into_words(entries: Vec<DictionaryEntry>) -> Vec<String> {
+    entries.into_iter().map(|entry| entry.word).collect()
+}
+
+/// Picks a word from `words` that isn't in `used` and adds it there. Once
+/// every word has been used, `used` starts over. With a weight for every
+/// word, each is picked with a chance in proportion to its weight, and
+/// otherwise they are all equally likely.
+fn random_word(
+    words: &[String],
+    weights: &[f64],
+    used: &mut HashSet<String>,
+    word_length: usize,
+    rng: &mut RandomNumberGenerator,
+) -> Result<String, GameError> {
+    let mut unused: Vec<usize> = (0..words.len())
+        .filter(|&i| !used.contains(&words[i]))
+        .collect();
+    if unused.is_empty() {
+        used.clear();
+        unused = (0..words.len()).collect();
+    }
+    let weighted = (weights.len() == words.len())
+        .then(|| weighted_pick(&unused.iter().map(|&i| weights[i]).collect::<Vec<_>>(), rng))
+        .flatten();
+    let index = weighted
+        .or_else(|| rng.random_slice_index(&unused))
+        .ok_or(GameError::EmptyDictionary { word_length })?;
+    let word = words[unused[index]].clone();
+    used.insert(word.clone());
+    Ok(word)
+}
+
+/// Picks an index into `weights` with a chance in proportion to its weight,
+/// or `None` if no weight is above zero.
+fn weighted_pick(weights: &[f64], rng: &mut RandomNumberGenerator) -> Option<usize> {
+    let total: f64 = weights.iter().filter(|weight| **weight > 0.0).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut roll = rng.range(0.0, total);
+    for (i, &weight) in weights.iter().enumerate() {
+        if weight <= 0.0 {
+            continue;
+        }
+        if roll < weight {
+            return Some(i);
+        }
+        roll -= weight;
+    }
+    // Rounding can leave a sliver past the last weight.
+    weights.iter().rposition(|weight| *weight > 0.0)
+}
+
+fn non_empty<T>(words: Vec<T>, word_length: usize) -> Result<Vec<T>, GameError> {
+    if words.is_empty() {
+        Err(GameError::EmptyDictionary { word_length })
+    } else {
+        Ok(words)
+    }
+}
+
+/// Sanitizes every line and keeps the first copy of each that is
+/// `word_length` letters.
+fn filter_words<'a>(
+    lines: impl Iterator<Item = &'a str>,
+    word_length: usize,
+    charset: Charset,
+) -> Vec<String> {
+    into_words(filter_entries(lines, word_length, charset))
+}
+
+/// [`filter_words`], keeping the frequency column of each line.
+fn filter_entries<'a>(
+    lines: impl Iterator<Item = &'a str>,
+    word_length: usize,
+    charset: Charset,
+) -> Vec<DictionaryEntry> {
+    let mut seen = HashSet::new();
+    lines
+        .map(|line| {
+            let (word, frequency) = split_frequency(line);
+            DictionaryEntry {
+                word: charset.sanitize(word),
+                frequency,
+            }
+        })
+        .filter(|entry| {
+            entry.word.chars().count() == word_length && seen.insert(entry.word.clone())
+        })
+        .collect()
+}
+
+/// The most common word length in `words`, counting letters rather than
+/// bytes. Ties go to the shorter length, and an empty list gives
+/// [`WORD_LENGTH`].
+#[must_use]
+pub fn infer_length(words: &[String]) -> usize {
+    length_histogram(words)
+        .into_iter()
+        .rev()
+        .max_by_key(|&(_, count)| count)
+        .map_or(WORD_LENGTH, |(length, _)| length)
+}
+
+/// How many of `words` there are of each length, counting letters rather
+/// than bytes.
+#[must_use]
+pub fn length_histogram(words: &[String]) -> BTreeMap<usize, usize> {
+    let mut counts = BTreeMap::new();
+    for word in words {
+        *counts.entry(word.chars().count()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Every word in a word list of one word per line, whatever its length,
+/// sanitized for `charset` and kept once. A frequency column is dropped.
+#[must_use]
+pub fn dictionary_words(text: &str, charset: Charset) -> Vec<String> {
+    all_words(text.lines(), charset)
+}
+
+/// Every word of the embedded dictionary, whatever its length.
+#[must_use]
+pub fn builtin_words() -> Vec<String> {
+    all_words(skip_header(ALL_WORDS.lines()), Charset::Ascii)
+}
+
+fn all_words<'a>(lines: impl Iterator<Item = &'a str>, charset: Charset) -> Vec<String> {
+    let mut seen = HashSet::new();
+    lines
+        .map(|line| charset.sanitize(split_frequency(line).0))
+        .filter(|word| !word.is_empty() && seen.insert(word.clone()))
+        .collect()
+}
+
+/// Uppercases `word` and strips everything that isn't an ASCII letter.
+#[must_use]
+pub fn sanitize_word(word: &str) -> String {
+    word.trim()
+        .to_uppercase()
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .collect()
+}
+
+/// Uppercases `phrase` and strips everything that isn't an ASCII letter,
+/// except for a single space between words.
+#[must_use]
+pub fn sanitize_phrase(phrase: &str) -> String {
+    phrase
+        .split_whitespace()
+        .map(sanitize_word)
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reads a file of phrases, one per line, such as `ICE CREAM`. Phrases of
+/// any length are kept, since the space already makes each one distinct.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read or has no phrases.
+pub fn load_phrase_file(path: impl AsRef<Path>) -> Result<Vec<String>, GameError> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("couldn't read phrases {}: {e}", path.display()),
+        )
+    })?;
+    let mut seen = HashSet::new();
+    let phrases: Vec<String> = text
+        .lines()
+        .map(|line| sanitize_phrase(split_frequency(line).0))
+        .filter(|phrase| !phrase.is_empty() && seen.insert(phrase.clone()))
+        .collect();
+    if phrases.is_empty() {
+        return Err(GameError::NoPhrases);
+    }
+    Ok(phrases)
+}
+
+/// Reads a list of words that must not be played, one per line, whatever
+/// their length, sanitized for `charset`.
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read.
+pub fn load_blocklist(
+    path: impl AsRef<Path>,
+    charset: Charset,
+) -> Result<HashSet<String>, GameError> {
+    let path = path.as_ref();
+    let text = fs::read_to_string(path).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("couldn't read blocklist {}: {e}", path.display()),
+        )
+    })?;
+    Ok(dictionary_words(&text, charset).into_iter().collect())
+}
+
+/// Removes the words in `blocklist` from `entries`, keeping the rest in
+/// order.
+pub fn remove_blocked<S: BuildHasher>(
+    entries: &mut Vec<DictionaryEntry>,
+    blocklist: &HashSet<String, S>,
+) {
+    entries.retain(|entry| !blocklist.contains(&entry.word));
+}
+
+/// Keeps only the `count` most frequent of `entries`, in their original
+/// order. Words without a frequency count as the rarest, and ties go to the
+/// word listed first. Without any frequencies every entry is kept, since
+/// there is nothing to rank them by.
+pub fn keep_most_common(entries: &mut Vec<DictionaryEntry>, count: usize) {
+    if entries.len() <= count || entries.iter().all(|entry| entry.frequency.is_none()) {
+        return;
+    }
+    let mut ranked: Vec<usize> = (0..entries.len()).collect();
+    ranked.sort_by_key(|&i| std::cmp::Reverse(entries[i].frequency.unwrap_or(0)));
+    let kept: HashSet<usize> = ranked.into_iter().take(count).collect();
+    let mut index = 0;
+    entries.retain(|_| {
+        index += 1;
+        kept.contains(&(index - 1))
+    });
+}
+
+/// Every letter used in `words`, sorted and listed once. Spaces between the
+/// words of phrases don't count.
+pub fn alphabet_of<'a>(words: impl IntoIterator<Item = &'a String>) -> Vec<char> {
+    let letters: BTreeSet<char> = words
+        .into_iter()
+        .flat_map(|word| word.chars())
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    letters.into_iter().collect()
+}
+
+/// Whether `a` and `b` have the same length and their spaces in the same
+/// places, so one can be guessed for the other.
+fn same_shape(a: &str, b: &str) -> bool {
+    a.chars().count() == b.chars().count()
+        && a.chars()
+            .zip(b.chars())
+            .all(|(x, y)| (x == ' ') == (y == ' '))
+}
+
+/// `word` with its letters blanked out, e.g. `___ _____`.
+fn shape_of(word: &str) -> String {
+    word.chars()
+        .map(|c| if c == ' ' { ' ' } else { '_' })
+        .collect()
+}
+
+/// Uppercases `word` and strips everything that isn't a letter in any
+/// script, so accented letters like É or Ñ are kept.
+#[must_use]
+pub fn sanitize_word_unicode(word: &str) -> String {
+    word.trim()
+        .to_uppercase()
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .collect()
+}
+
+/// Which letters words may contain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Charset {
+    /// Only the English letters A to Z.
+    #[default]
+    Ascii,
+    /// Any Unicode letter, for dictionaries in other languages.
+    Unicode,
+}
+
+impl Charset {
+    /// Sanitizes `word` with [`sanitize_word`] or [`sanitize_word_unicode`].
+    #[must_use]
+    pub fn sanitize(self, word: &str) -> String {
+        match self {
+            Charset::Ascii => sanitize_word(word),
+            Charset::Unicode => sanitize_word_unicode(word),
+        }
+    }
+
+    /// Whether `c` is a letter that words may contain.
+    fn is_letter(self, c: char) -> bool {
+        match self {
+            Charset::Ascii => c.is_ascii_alphabetic(),
+            Charset::Unicode => c.is_alphabetic(),
+        }
+    }
+}
+
+/// How obscure the hidden word may be. Only word lists with a frequency
+/// column can be narrowed down; without one every word is a possible answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Difficulty {
+    /// Only the most common quarter of the words.
+    Easy,
+    /// The most common half of the words.
+    Medium,
+    /// Every word, however rare.
+    #[default]
+    Hard,
+}
+
+impl Difficulty {
+    /// Every difficulty, in the order they are listed.
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+    /// The names accepted by [`Difficulty::from_name`], in the order of
+    /// [`Difficulty::ALL`].
+    pub const NAMES: [&'static str; 3] = [
+        Self::ALL[0].name(),
+        Self::ALL[1].name(),
+        Self::ALL[2].name(),
+    ];
+
+    /// Looks up a difficulty by name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Difficulty> {
+        Self::ALL.into_iter().find(|value| value.name() == name)
+    }
+
+    /// The name [`Difficulty::from_name`] looks this difficulty up by.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    /// Picks the possible answers out of `entries`: the words at least as
+    /// frequent as the threshold for this difficulty. Words without a
+    /// frequency count as rare.
+    #[must_use]
+    pub fn answers(self, entries: &[DictionaryEntry]) -> Vec<String> {
+        let mut frequencies: Vec<u64> = entries.iter().filter_map(|e| e.frequency).collect();
+        let keep = match self {
+            Difficulty::Easy => frequencies.len().div_ceil(4),
+            Difficulty::Medium => frequencies.len().div_ceil(2),
+            Difficulty::Hard => 0,
+        };
+        if keep == 0 {
+            return entries.iter().map(|entry| entry.word.clone()).collect();
+        }
+        frequencies.sort_unstable_by(|a, b| b.cmp(a));
+        let threshold = frequencies[keep - 1];
+        entries
+            .iter()
+            .filter(|entry| entry.frequency.is_some_and(|f| f >= threshold))
+            .map(|entry| entry.word.clone())
+            .collect()
+    }
+}
+
+/// What the player is told about each guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mode {
+    /// Every letter is marked correct, misplaced or absent.
+    #[default]
+    Letters,
+    /// Only the edit distance between the guess and the word is shown,
+    /// e.g. `3 edits away`.
+    Distance,
+}
+
+impl Mode {
+    /// Every mode, in the order they are listed.
+    pub const ALL: [Mode; 2] = [Mode::Letters, Mode::Distance];
+
+    /// The names accepted by [`Mode::from_name`], in the order of
+    /// [`Mode::ALL`].
+    pub const NAMES: [&'static str; 2] = [Self::ALL[0].name(), Self::ALL[1].name()];
+
+    /// Looks up a mode by name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Mode> {
+        Self::ALL.into_iter().find(|value| value.name() == name)
+    }
+
+    /// The name [`Mode::from_name`] looks this mode up by.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Mode::Letters => "letters",
+            Mode::Distance => "distance",
+        }
+    }
+}
+
+/// How a single letter of a guess matches the hidden word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LetterStatus {
+    /// The letter is in the word, in this exact position.
+    Correct,
+    /// The letter is in the word, but somewhere else.
+    Misplaced,
+    /// The letter is not in the word (or all its occurrences are accounted
+    /// for).
+    Absent,
+}
+
+impl LetterStatus {
+    /// Orders statuses by how much they reveal, so the keyboard can keep the
+    /// best one known for each letter.
+    fn rank(self) -> u8 {
+        match self {
+            LetterStatus::Absent => 0,
+            LetterStatus::Misplaced => 1,
+            LetterStatus::Correct => 2,
+        }
+    }
+
+    fn from_rank(rank: u32) -> LetterStatus {
+        match rank {
+            0 => LetterStatus::Absent,
+            1 => LetterStatus::Misplaced,
+            _ => LetterStatus::Correct,
+        }
+    }
+
+    /// The lowercase name used for this status in JSON output.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            LetterStatus::Correct => "correct",
+            LetterStatus::Misplaced => "misplaced",
+            LetterStatus::Absent => "absent",
+        }
+    }
+}
+
+/// A guess that has been played, along with how each of its letters scored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    pub word: String,
+    pub statuses: Vec<LetterStatus>,
+}
+
+impl Guess {
+    /// The guess colored letter by letter with the colors of `theme`.
+    #[must_use]
+    pub fn colorize(&self, theme: Theme) -> Vec<ColoredString> {
+        self.word
+            .chars()
+            .zip(&self.statuses)
+            .map(|(c, status)| match c {
+                PEEK_PLACEHOLDER => c.to_string().normal(),
+                _ => theme.paint(&c.to_string(), *status),
+            })
+            .collect()
+    }
+}
+
+/// How a game played by [`RustleGame::autoplay`] went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoplayResult {
+    pub won: bool,
+    pub tries: usize,
+}
+
+/// Where a game stands after the latest guess.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameStatus {
+    /// The word was guessed in `tries` guesses.
+    Won { tries: usize },
+    /// The tries ran out before the word was guessed.
+    Lost { word: String },
+    /// There are guesses left.
+    InProgress,
+}
+
+/// What the player asked for at the guess prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessAction {
+    /// A valid guess, which has already been recorded.
+    Word(String),
+    /// Give up and show the word. Counts as a loss.
+    Reveal,
+    /// Leave the game without recording a result.
+    Quit,
+}
+
+#[allow(clippy::struct_excessive_bools)]
+pub struct RustleGame {
+    /// The words the hidden word is drawn from.
+    answers: Vec<String>,
+    /// The words accepted as guesses, every answer among them.
+    valid_guesses: Vec<String>,
+    /// Both word lists, to look guesses up quickly. The lists themselves are
+    /// kept for their order and to pick words at random.
+    guessable: HashSet<String>,
+    word: String,
+    word_length: usize,
+    charset: Charset,
+    max_tries: usize,
+    /// Whether the game goes on until the word is found, however many
+    /// guesses it takes.
+    zen: bool,
+    guessed_letters: HashSet<char>,
+    keyboard_state: HashMap<char, LetterStatus>,
+    hard_mode: bool,
+    render_mode: RenderMode,
+    numbering: Numbering,
+    mode: Mode,
+    theme: Theme,
+    timed: bool,
+    /// Whether the word is a phrase whose words are separated by spaces.
+    phrases: bool,
+    /// Whether the player may ask for letter frequencies with `/freq`.
+    frequency_hints: bool,
+    /// Whether to point out letters that are already known to be absent.
+    assist: bool,
+    /// Whether to count the answers that still fit after every guess, and
+    /// offer the solver's pick, in dimmed text.
+    coach: bool,
+    /// Whether the first guess has to have all different letters.
+    distinct_first: bool,
+    /// Whether the player may spend a guess on `/peek`.
+    peeks: bool,
+    /// Whether guesses that aren't in either word list are accepted.
+    allow_any: bool,
+    /// Whether the player may ask for the likeliest letters at each
+    /// position with `/positions`.
+    position_hints: bool,
+    /// Whether the keyboard and the letters known not to be in the word
+    /// are shown.
+    hints_enabled: bool,
+    /// Whether the word is kept out of the message when the game is lost.
+    hide_answer: bool,
+    /// Whether the first prompt explains what the colors mean.
+    legend: bool,
+    /// Whether this is the daily game, where guesses can't be undone.
+    daily: bool,
+    /// Whether the player may still take back a guess with `/mulligan`.
+    mulligan_available: bool,
+    /// Whether a guess has been taken back with `/mulligan`.
+    mulligan_used: bool,
+    /// The words played so far, including this one, which aren't picked
+    /// again until every answer has been played.
+    used_words: HashSet<String>,
+    /// How likely each of the answers is to be picked by
+    /// [`reset_with_new_word`](Self::reset_with_new_word), if not all the
+    /// same.
+    answer_weights: Vec<f64>,
+    /// Reads guesses, with the history of this game only.
+    editor: LineEditor,
+    /// Every letter used by the word lists that the charset allows, in
+    /// order.
+    alphabet: Vec<char>,
+    started: Option<Instant>,
+    guesses: Vec<Guess>,
+}
+
+impl RustleGame {
+    /// Starts a new game with a random word from the embedded dictionary.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dictionary is empty.
+    pub fn new() -> Result<Self, GameError> {
+        Self::builder().build()
+    }
+
+    /// Returns a builder to set up a game with several options at once.
+    #[must_use]
+    pub fn builder() -> RustleGameBuilder {
+        RustleGameBuilder::default()
+    }
+
+    /// Starts a new game with a random `word_length`-letter word from the
+    /// embedded dictionary.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dictionary has no words of that length.
+    pub fn with_length(word_length: usize) -> Result<Self, GameError> {
+        Self::builder().length(word_length).build()
+    }
+
+    /// Starts a new game whose word is picked by an RNG seeded with `seed`, so
+    /// the same seed always gives the same word.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dictionary is empty.
+    pub fn with_seed(seed: u64) -> Result<Self, GameError> {
+        Self::builder().seed(seed).build()
+    }
+
+    /// Starts today's game: everyone playing on the same (UTC) day gets the
+    /// same word.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dictionary is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the system clock is set before the Unix epoch.
+    pub fn daily() -> Result<Self, GameError> {
+        Self::builder().daily(true).build()
+    }
+
+    /// Starts a new game with a random word from the dictionary file at
+    /// `path`, which lists one word per line. Only the words with
+    /// `word_length` letters are used. With [`Charset::Unicode`] accented
+    /// letters are kept, both in the dictionary and in guesses.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or has no words of that
+    /// length.
+    pub fn from_dictionary_file(
+        path: impl AsRef<Path>,
+        word_length: usize,
+        charset: Charset,
+    ) -> Result<Self, GameError> {
+        let words = load_dictionary_file(path, word_length, charset)?;
+        Self::builder()
+            .length(word_length)
+            .charset(charset)
+            .dictionary(words)
+            .build()
+    }
+
+    /// Starts a new game whose word is drawn from `answers`. Any word in
+    /// `valid_guesses` is accepted as a guess, so the guess list can be much
+    /// larger than the curated list of answers. If any word has letters
+    /// other than A to Z, guesses may use any letter, as with
+    /// [`Charset::Unicode`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `answers` is empty, or one of them isn't in
+    /// `valid_guesses`.
+    pub fn from_word_lists(
+        answers: Vec<String>,
+        valid_guesses: Vec<String>,
+        word_length: usize,
+    ) -> Result<Self, GameError> {
+        let charset = if answers
+            .iter()
+            .chain(&valid_guesses)
+            .all(|word| word.is_ascii())
+        {
+            Charset::Ascii
+        } else {
+            Charset::Unicode
+        };
+        Self::builder()
+            .length(word_length)
+            .charset(charset)
+            .answers(answers)
+            .dictionary(valid_guesses)
+            .build()
+    }
+
+    /// Starts a new game whose hidden word is `word`, e.g. for a puzzle made
+    /// up by hand. The word is sanitized and has to be in `dictionary`,
+    /// whose words are also the ones accepted as guesses.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `word` isn't in `dictionary`.
+    pub fn with_word(word: &str, dictionary: Vec<String>) -> Result<Self, GameError> {
+        Self::builder().dictionary(dictionary).word(word).build()
+    }
+
+    /// Starts a new game with the given `word`, e.g. to retry a word that
+    /// was just lost. Any word in `dictionary` is accepted as a guess.
+    #[must_use]
+    pub fn replay_of(word: String, dictionary: Vec<String>) -> Self {
+        let word_length = word.chars().count();
+        Self::from_parts(dictionary.clone(), dictionary, word, word_length)
+    }
+
+    /// Starts a new game with a random phrase from `phrases`. Guesses must
+    /// be phrases of the same length with spaces in the same places, and the
+    /// spaces always score as correct.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `phrases` is empty.
+    pub fn from_phrases(phrases: Vec<String>) -> Result<Self, GameError> {
+        Self::builder().phrases(phrases).build()
+    }
+
+    /// Starts this game over with the same word, word lists and settings,
+    /// but none of the guesses.
+    #[must_use]
+    pub fn replay(&self) -> Self {
+        Self {
+            charset: self.charset,
+            max_tries: self.max_tries,
+            zen: self.zen,
+            hard_mode: self.hard_mode,
+            render_mode: self.render_mode,
+            numbering: self.numbering,
+            mode: self.mode,
+            theme: self.theme,
+            timed: self.timed,
+            phrases: self.phrases,
+            frequency_hints: self.frequency_hints,
+            assist: self.assist,
+            coach: self.coach,
+            distinct_first: self.distinct_first,
+            peeks: self.peeks,
+            allow_any: self.allow_any,
+            position_hints: self.position_hints,
+            hints_enabled: self.hints_enabled,
+            hide_answer: self.hide_answer,
+            legend: self.legend,
+            daily: self.daily,
+            mulligan_available: self.mulligan_available && !self.mulligan_used,
+            used_words: self.used_words.clone(),
+            alphabet: self.alphabet.clone(),
+            answer_weights: self.answer_weights.clone(),
+            ..Self::from_parts(
+                self.answers.clone(),
+                self.valid_guesses.clone(),
+                self.word.clone(),
+                self.word_length,
+            )
+        }
+    }
+
+    /// Starts a new game in place with a word picked from the answers by
+    /// `rng`, keeping the word lists and settings. Unlike building a new
+    /// game, the word lists aren't loaded again, which makes it much faster
+    /// to play many games in a row, e.g. with [`autoplay`](Self::autoplay).
+    /// No word comes up twice until every answer has been played, and with
+    /// weights from the builder, common words come up more often.
+    pub fn reset_with_new_word(&mut self, rng: &mut RandomNumberGenerator) {
+        if let Ok(word) = random_word(
+            &self.answers,
+            &self.answer_weights,
+            &mut self.used_words,
+            self.word_length,
+            rng,
+        ) {
+            self.word = word;
+        }
+        self.word_length = self.word.chars().count();
+        self.guesses.clear();
+        self.guessed_letters.clear();
+        self.keyboard_state.clear();
+        self.started = None;
+        self.editor = LineEditor::default();
+    }
+
+    fn from_parts(
+        answers: Vec<String>,
+        valid_guesses: Vec<String>,
+        word: String,
+        word_length: usize,
+    ) -> Self {
+        let alphabet = Self::letters_of(&answers, &valid_guesses, Charset::default());
+        let guessable = answers.iter().chain(&valid_guesses).cloned().collect();
+        let used_words = HashSet::from([word.clone()]);
+        Self {
+            answers,
+            valid_guesses,
+            guessable,
+            word,
+            word_length,
+            charset: Charset::default(),
+            max_tries: MAX_TRIES,
+            zen: false,
+            hard_mode: false,
+            render_mode: RenderMode::default(),
+            numbering: Numbering::default(),
+            mode: Mode::default(),
+            theme: Theme::default(),
+            timed: false,
+            phrases: false,
+            frequency_hints: false,
+            assist: false,
+            coach: false,
+            distinct_first: false,
+            peeks: false,
+            allow_any: false,
+            position_hints: false,
+            hints_enabled: true,
+            hide_answer: false,
+            legend: true,
+            daily: false,
+            mulligan_available: false,
+            mulligan_used: false,
+            used_words,
+            answer_weights: Vec::new(),
+            editor: LineEditor::default(),
+            alphabet,
+            started: None,
+            guessed_letters: HashSet::new(),
+            keyboard_state: HashMap::new(),
+            guesses: Vec::new(),
+        }
+    }
+
+    /// The words played this session, including this one. Pass them to
+    /// [`RustleGameBuilder::used_words`] so the next game picks another.
+    #[must_use]
+    pub fn used_words(&self) -> &HashSet<String> {
+        &self.used_words
+    }
+
+    /// Sets how many guesses the player gets before losing.
+    #[must_use]
+    pub fn with_max_tries(mut self, max_tries: usize) -> Self {
+        self.max_tries = max_tries;
+        self
+    }
+
+    /// Turns zen mode on or off. In zen mode there is no limit on the
+    /// guesses, so the game only ends when the word is found or given up.
+    #[must_use]
+    pub fn with_zen(mut self, zen: bool) -> Self {
+        self.zen = zen;
+        self
+    }
+
+    /// Whether zen mode is on.
+    #[must_use]
+    pub fn zen(&self) -> bool {
+        self.zen
+    }
+
+    /// Every letter that appears in the game's word lists and can be typed
+    /// with its charset, in order. It decides which keys the keyboard
+    /// shows.
+    #[must_use]
+    pub fn alphabet(&self) -> &[char] {
+        &self.alphabet
+    }
+
+    /// The number of guesses the player gets before losing. It doesn't
+    /// apply in zen mode.
+    #[must_use]
+    pub fn max_tries(&self) -> usize {
+        self.max_tries
+    }
+
+    /// How many guesses the player has left: [`usize::MAX`] in zen mode.
+    #[must_use]
+    pub fn remaining_guesses(&self) -> usize {
+        if self.zen {
+            usize::MAX
+        } else {
+            self.max_tries.saturating_sub(self.guesses.len())
+        }
+    }
+
+    /// The total number of guesses, as shown to the player: `∞` in zen
+    /// mode.
+    fn tries_label(&self) -> String {
+        if self.zen {
+            "∞".to_string()
+        } else {
+            self.max_tries.to_string()
+        }
+    }
+
+    /// Turns hard mode on or off. In hard mode every revealed hint has to be
+    /// used in the following guesses.
+    #[must_use]
+    pub fn with_hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    /// Whether hard mode is on.
+    #[must_use]
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    /// Lets the player ask how many remaining candidates contain each
+    /// letter with `/freq`.
+    #[must_use]
+    pub fn with_frequency_hints(mut self, frequency_hints: bool) -> Self {
+        self.frequency_hints = frequency_hints;
+        self
+    }
+
+    /// Turns on a note when a guess reuses a letter already known not to be
+    /// in the word, and a count of the answers that still fit the clues
+    /// after every guess. The guess is still played.
+    #[must_use]
+    pub fn with_assist(mut self, assist: bool) -> Self {
+        self.assist = assist;
+        self
+    }
+
+    /// Turns on the coach: after every guess, a dimmed note says how many
+    /// answers still fit the clues and that `/hint` gives the most
+    /// informative next guess. The player still picks every guess.
+    #[must_use]
+    pub fn with_coach(mut self, coach: bool) -> Self {
+        self.coach = coach;
+        self
+    }
+
+    /// Requires the opening guess to have no repeated letters, since an
+    /// opener with all different letters tells more about the word.
+    #[must_use]
+    pub fn with_distinct_first(mut self, distinct_first: bool) -> Self {
+        self.distinct_first = distinct_first;
+        self
+    }
+
+    /// Lets the player spend a guess on `/peek`, up to [`MAX_PEEKS`] times.
+    #[must_use]
+    pub fn with_peeks(mut self, peeks: bool) -> Self {
+        self.peeks = peeks;
+        self
+    }
+
+    /// Lets the player ask for the likeliest letters at each position of
+    /// the word with `/positions`.
+    #[must_use]
+    pub fn with_position_hints(mut self, position_hints: bool) -> Self {
+        self.position_hints = position_hints;
+        self
+    }
+
+    /// Shows the keyboard and the letters known not to be in the word, as
+    /// it does by default. Turning them off leaves only the board.
+    #[must_use]
+    pub fn with_hints_enabled(mut self, hints_enabled: bool) -> Self {
+        self.hints_enabled = hints_enabled;
+        self
+    }
+
+    /// Leaves the word out of the [`loss_message`](Self::loss_message), e.g.
+    /// so a lost daily game played on a stream doesn't spoil it for others.
+    #[must_use]
+    pub fn with_hide_answer(mut self, hide_answer: bool) -> Self {
+        self.hide_answer = hide_answer;
+        self
+    }
+
+    /// Sets whether the first prompt, before any guess, explains how
+    /// correct, misplaced and absent letters are shown. On by default.
+    #[must_use]
+    pub fn with_legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    /// The word, to show once the game is lost or given up, or `None` if it
+    /// is hidden.
+    #[must_use]
+    pub fn revealed_word(&self) -> Option<&str> {
+        (!self.hide_answer).then_some(self.word.as_str())
+    }
+
+    /// What to tell the player when the tries run out: the word, unless it
+    /// is hidden.
+    #[must_use]
+    pub fn loss_message(&self) -> String {
+        match self.revealed_word() {
+            Some(word) => format!("You ran out of tries! The word was {word}"),
+            None => "Out of tries!".to_string(),
+        }
+    }
+
+    /// Marks this as the daily game, where `/undo` isn't allowed, so a bad
+    /// guess can only be taken back with a mulligan.
+    #[must_use]
+    pub fn with_daily(mut self, daily: bool) -> Self {
+        self.daily = daily;
+        self
+    }
+
+    /// Whether this is the daily game.
+    #[must_use]
+    pub fn is_daily(&self) -> bool {
+        self.daily
+    }
+
+    /// Gives the player a mulligan: in the daily game, one guess can be
+    /// taken back with `/mulligan` without using up a try. Whether one is
+    /// left for the day has to be tracked by the caller, see
+    /// [`mulligan_used`](Self::mulligan_used).
+    #[must_use]
+    pub fn with_mulligan(mut self, available: bool) -> Self {
+        self.mulligan_available = available;
+        self
+    }
+
+    /// Whether the mulligan has been spent in this game.
+    #[must_use]
+    pub fn mulligan_used(&self) -> bool {
+        self.mulligan_used
+    }
+
+    /// Accepts any guess of the right length, even if it isn't in the word
+    /// lists. Such guesses are scored like any other.
+    #[must_use]
+    pub fn with_allow_any(mut self, allow_any: bool) -> Self {
+        self.allow_any = allow_any;
+        self
+    }
+
+    /// Sets which letters guesses may contain. This should match the charset
+    /// the word lists were loaded with.
+    #[must_use]
+    pub fn with_charset(mut self, charset: Charset) -> Self {
+        self.charset = charset;
+        self.alphabet = Self::letters_of(&self.answers, &self.valid_guesses, charset);
+        self
+    }
+
+    /// The letters of the word lists that can be typed with `charset`.
+    fn letters_of(answers: &[String], valid_guesses: &[String], charset: Charset) -> Vec<char> {
+        alphabet_of(answers.iter().chain(valid_guesses))
+            .into_iter()
+            .filter(|&c| charset.is_letter(c))
+            .collect()
+    }
+
+    /// Turns the clock shown at every guess prompt on or off.
+    #[must_use]
+    pub fn with_timer(mut self, timed: bool) -> Self {
+        self.timed = timed;
+        self
+    }
+
+    /// How long the game has been going, counted from the first prompt or
+    /// guess. `None` if it hasn't started yet.
+    #[must_use]
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.started.map(|started| started.elapsed())
+    }
+
+    /// Sets how guesses are displayed.
+    #[must_use]
+    pub fn with_render_mode(mut self, render_mode: RenderMode) -> Self {
+        self.render_mode = render_mode;
+        self
+    }
+
+    /// Changes what the player is told about each guess. In
+    /// [`Mode::Distance`] the board only shows how many edits away every
+    /// guess is, and the keyboard and the absent letters are hidden.
+    #[must_use]
+    pub fn with_mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    #[must_use]
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// How many edits away from the word `guess` is.
+    #[must_use]
+    pub fn distance(&self, guess: &str) -> usize {
+        solver::edit_distance(guess, &self.word)
+    }
+
+    /// Changes how the rows of the board and the prompt count guesses.
+    #[must_use]
+    pub fn with_numbering(mut self, numbering: Numbering) -> Self {
+        self.numbering = numbering;
+        self
+    }
+
+    /// Sets the colors used to show how letters scored.
+    #[must_use]
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// The hidden word.
+    #[must_use]
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    /// The number of letters in the hidden word.
+    #[must_use]
+    pub fn word_length(&self) -> usize {
+        self.word_length
+    }
+
+    /// The guesses made so far, in the order they were played.
+    #[must_use]
+    pub fn guesses(&self) -> &[Guess] {
+        &self.guesses
+    }
+
+    /// Scores `guess` against the hidden word and records it, without any
+    /// validation or terminal IO. Returns the guess that was added.
+    pub fn guess(&mut self, guess: &str) -> &Guess {
+        self.started.get_or_insert_with(Instant::now);
+        let statuses = self.score_guess(guess);
+        self.guesses.push(Guess {
+            word: guess.to_string(),
+            statuses,
+        });
+        &self.guesses[self.guesses.len() - 1]
+    }
+
+    /// Plays one turn from raw player input: sanitizes and validates it,
+    /// records it if it is allowed, and returns where the game stands.
+    ///
+    /// # Errors
+    ///
+    /// Returns why the guess was rejected, in which case nothing is recorded.
+    pub fn submit(&mut self, input: &str) -> Result<GameStatus, InvalidGuess> {
+        let guess = self.parse_guess(input)?;
+        self.validate(&guess)?;
+        self.guess(&guess);
+        Ok(self.check_status())
+    }
+
+    /// Scores raw player input against the hidden word without playing it,
+    /// e.g. to keep exploring once the game is over. The guess has to be a
+    /// word of the right length, but the hard mode rules don't apply and no
+    /// try is used up.
+    ///
+    /// # Errors
+    ///
+    /// Returns why the input isn't a guess that could be played.
+    pub fn explore(&self, input: &str) -> Result<Guess, InvalidGuess> {
+        let guess = self.parse_guess(input)?;
+        self.check_word(&guess)?;
+        Ok(Guess {
+            statuses: self.evaluate_guess(&guess),
+            word: guess,
+        })
+    }
+
+    /// Scores `guess` against the hidden word, letter by letter, without
+    /// touching any game state.
+    #[must_use]
+    pub fn evaluate_guess(&self, guess: &str) -> Vec<LetterStatus> {
+        evaluate(&self.word, guess)
+    }
+
+    /// Lets the solver play the rest of the game on its own, always guessing
+    /// its suggestion, until the word is found or the tries run out (in zen
+    /// mode, until no word fits the clues). Never
+    /// reads from stdin.
+    pub fn autoplay(&mut self) -> AutoplayResult {
+        while self.remaining_guesses() > 0 {
+            let Some(guess) = self.suggest() else {
+                break;
+            };
+            self.guess(&guess);
+            if guess == self.word {
+                return AutoplayResult {
+                    won: true,
+                    tries: self.guesses.len(),
+                };
+            }
+        }
+        AutoplayResult {
+            won: false,
+            tries: self.guesses.len(),
+        }
+    }
+
+    /// Every answer that still fits the clues of all guesses so far.
+    #[must_use]
+    pub fn candidates(&self) -> Vec<String> {
+        solver::filter_candidates(&self.possible_answers(), &self.guesses)
+    }
+
+    /// Every word in the game's word lists that fits `pattern`, such as
+    /// `CR.N.`; see [`solver::matching`].
+    #[must_use]
+    pub fn matching(&self, pattern: &str) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let words: Vec<String> = self
+            .valid_guesses
+            .iter()
+            .chain(&self.answers)
+            .filter(|word| seen.insert(*word))
+            .cloned()
+            .collect();
+        solver::matching(&words, pattern)
+    }
+
+    /// Rates the latest guess by how many of the words that still fitted
+    /// the clues before it were ruled out, against the solver's pick at that
+    /// point; see [`solver::rate_guess`]. Also returns how many words are
+    /// left, and how many the solver's pick would have left. `None` before
+    /// the first guess.
+    #[must_use]
+    pub fn rate_last_guess(&self) -> Option<(solver::Rating, usize, usize)> {
+        let (last, earlier) = self.guesses.split_last()?;
+        let before = solver::filter_candidates(&self.possible_answers(), earlier);
+        let after = before
+            .iter()
+            .filter(|word| solver::is_consistent(word, last))
+            .count();
+        let best_after = solver::best_guess(&before).map_or(after, |best| {
+            before.len() - solver::eliminated(&self.word, &best, &before)
+        });
+        Some((
+            solver::rate_guess(before.len(), after, best_after),
+            after,
+            best_after,
+        ))
+    }
+
+    /// The answers shaped like the hidden word, before any clues.
+    fn possible_answers(&self) -> Vec<String> {
+        self.answers
+            .iter()
+            .filter(|word| same_shape(word, &self.word))
+            .cloned()
+            .collect()
+    }
+
+    /// Rates up to `sample` opening guesses by how many answers their clues
+    /// would rule out for this game's word, and on average over every
+    /// answer, best first. It ignores the guesses made so far, and is slow
+    /// for big word lists, since every rated guess is scored against every
+    /// answer.
+    #[must_use]
+    pub fn explain_openings(&self, sample: usize) -> Vec<solver::Opening> {
+        let guesses: Vec<String> = self
+            .valid_guesses
+            .iter()
+            .filter(|word| same_shape(word, &self.word))
+            .cloned()
+            .collect();
+        solver::explain_openings(&self.word, &guesses, &self.possible_answers(), sample)
+    }
+
+    /// Rates the answers shaped like the hidden word as opening guesses, by
+    /// how many answers they leave on average, best first. With `sample`
+    /// only that many answers are used, since every one is scored against
+    /// every other.
+    #[must_use]
+    pub fn best_openers(&self, sample: Option<usize>) -> Vec<solver::Opener> {
+        solver::best_openers(&self.possible_answers(), sample)
+    }
+
+    /// The solver's pick for the next guess, if any word fits the clues.
+    #[must_use]
+    pub fn suggest(&self) -> Option<String> {
+        solver::best_guess(&self.candidates())
+    }
+
+    /// The letters of `guess` that earlier guesses showed aren't in the
+    /// word, each listed once in the order they appear.
+    #[must_use]
+    pub fn reused_absent_letters(&self, guess: &str) -> Vec<char> {
+        let mut reused = Vec::new();
+        for c in guess.chars() {
+            // A letter can be absent in one spot and still be in the word
+            // elsewhere, when a guess has more copies than the word.
+            let known_absent = self.guessed_letters.contains(&c)
+                && self.keyboard_state.get(&c) == Some(&LetterStatus::Absent);
+            if known_absent && !reused.contains(&c) {
+                reused.push(c);
+            }
+        }
+        reused
+    }
+
+    /// How many of the remaining candidates contain each letter that hasn't
+    /// been guessed yet, most common first. Only counts are given, so the
+    /// answer itself isn't revealed.
+    #[must_use]
+    pub fn letter_frequencies(&self) -> Vec<(char, usize)> {
+        let mut frequencies: Vec<(char, usize)> = solver::letter_frequency(&self.candidates())
+            .into_iter()
+            .filter(|(c, _)| *c != ' ' && !self.keyboard_state.contains_key(c))
+            .collect();
+        frequencies.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        frequencies
+    }
+
+    /// The share of the remaining candidates that have each letter at each
+    /// position, from 0 to 1, likeliest first. Spaces between the words of
+    /// phrases are left out. `None` until a guess has been made, since
+    /// before that every word is still possible and the odds tell nothing.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn position_odds(&self) -> Option<Vec<Vec<(char, f64)>>> {
+        if self.guesses.is_empty() {
+            return None;
+        }
+        let candidates = self.candidates();
+        let total = candidates.len() as f64;
+        Some(
+            solver::position_frequency(&candidates)
+                .into_iter()
+                .map(|counts| {
+                    counts
+                        .into_iter()
+                        .filter(|(c, _)| *c != ' ')
+                        .map(|(c, count)| (c, count as f64 / total))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    /// Colors each letter of `guess` by how it matches the hidden word and
+    /// remembers the letters that turned out not to be in it.
+    pub fn colorize_guess(&mut self, guess: &str) -> Vec<ColoredString> {
+        let statuses = self.score_guess(guess);
+        Guess {
+            word: guess.to_string(),
+            statuses,
+        }
+        .colorize(self.theme)
+    }
+
+    fn score_guess(&mut self, guess: &str) -> Vec<LetterStatus> {
+        let statuses = self.evaluate_guess(guess);
+        self.record_letters(guess, &statuses);
+        statuses
+    }
+
+    fn record_letters(&mut self, guess: &str, statuses: &[LetterStatus]) {
+        guess
+            .chars()
+            .zip(statuses)
+            .filter(|(c, status)| **status == LetterStatus::Absent && *c != PEEK_PLACEHOLDER)
+            .for_each(|(c, _)| {
+                self.guessed_letters.insert(c);
+            });
+
+        for (c, status) in guess
+            .chars()
+            .zip(statuses)
+            .filter(|(c, _)| *c != ' ' && *c != PEEK_PLACEHOLDER)
+        {
+            let best = self.keyboard_state.entry(c).or_insert(*status);
+            if status.rank() > best.rank() {
+                *best = *status;
+            }
+        }
+    }
+
+    /// How many peeks have been spent so far.
+    #[must_use]
+    pub fn peeks_used(&self) -> usize {
+        self.guesses
+            .iter()
+            .filter(|guess| guess.word.contains(PEEK_PLACEHOLDER))
+            .count()
+    }
+
+    /// Reveals the leftmost letter of the word that no guess has found yet,
+    /// at its position. This uses up a guess: a row with only that letter
+    /// is added, and the other letters are shown as [`PEEK_PLACEHOLDER`].
+    /// Returns the position of the revealed letter.
+    ///
+    /// # Errors
+    ///
+    /// Returns why the peek isn't allowed: peeks are turned off or used up,
+    /// only one guess is left, or at most one letter is still unknown, so
+    /// peeking would give the word away.
+    pub fn peek(&mut self) -> Result<usize, String> {
+        if !self.peeks {
+            return Err("Peeking is turned off for this game.".to_string());
+        }
+        if self.peeks_used() >= MAX_PEEKS {
+            return Err(format!("You have used all {MAX_PEEKS} peeks."));
+        }
+        if self.remaining_guesses() < 2 {
+            return Err("You can't peek with only one guess left.".to_string());
+        }
+
+        let word: Vec<char> = self.word.chars().collect();
+        let unknown: Vec<usize> = (0..word.len())
+            .filter(|&pos| {
+                word[pos] != ' '
+                    && !self
+                        .guesses
+                        .iter()
+                        .any(|guess| guess.statuses.get(pos) == Some(&LetterStatus::Correct))
+            })
+            .collect();
+        if unknown.len() < 2 {
+            return Err("Peeking now would give the word away.".to_string());
+        }
+
+        let pos = unknown[0];
+        let row: String = word
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| {
+                if i == pos || c == ' ' {
+                    c
+                } else {
+                    PEEK_PLACEHOLDER
+                }
+            })
+            .collect();
+        let statuses: Vec<LetterStatus> = row
+            .chars()
+            .map(|c| match c {
+                PEEK_PLACEHOLDER => LetterStatus::Absent,
+                _ => LetterStatus::Correct,
+            })
+            .collect();
+        self.started.get_or_insert_with(Instant::now);
+        self.record_letters(&row, &statuses);
+        self.guesses.push(Guess {
+            word: row,
+            statuses,
+        });
+        Ok(pos)
+    }
+
+    /// Writes the word, the guesses and the settings that shape the board to
+    /// `path`, so the game can be picked up again with [`load`](Self::load).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file or its directory can't be written.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        SavedGame::of(self).save(path)
+    }
+
+    /// Picks up the game saved at `path`, with the word lists and other
+    /// settings of `builder`. The saved guesses are scored again against the
+    /// saved word, which rebuilds the board, the keyboard and the letters
+    /// known not to be in the word. Returns `None` if no game is saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't a saved game, or
+    /// if the saved word isn't in the word lists of `builder`.
+    pub fn load(path: &Path, builder: &RustleGameBuilder) -> Result<Option<Self>, GameError> {
+        let Some(saved) = SavedGame::load(path)? else {
+            return Ok(None);
+        };
+        let mut game = builder
+            .clone()
+            .word(&saved.word)
+            .max_tries(saved.max_tries)
+            .zen(saved.zen)
+            .hard_mode(saved.hard_mode)
+            .mode(saved.mode)
+            .build()?;
+        for guess in saved.guesses {
+            game.record_letters(&guess.word, &guess.statuses);
+            game.guesses.push(guess);
+        }
+        if !game.guesses.is_empty() {
+            game.started = Some(Instant::now());
+        }
+        Ok(Some(game))
+    }
+
+    /// Takes back the most recent guess, as if it was never played. Returns
+    /// whether there was a guess to take back.
+    pub fn undo(&mut self) -> bool {
+        if self.guesses.pop().is_none() {
+            return false;
+        }
+
+        self.guessed_letters.clear();
+        self.keyboard_state.clear();
+        for guess in std::mem::take(&mut self.guesses) {
+            self.record_letters(&guess.word, &guess.statuses);
+            self.guesses.push(guess);
+        }
+        true
+    }
+
+    /// Takes back the most recent guess of the daily game, as if it was
+    /// never played, spending the mulligan.
+    ///
+    /// # Errors
+    ///
+    /// Returns why it isn't allowed: this isn't the daily game, there is no
+    /// mulligan left, or nothing has been guessed yet.
+    pub fn mulligan(&mut self) -> Result<(), String> {
+        if !self.daily {
+            return Err("Mulligans are only for the daily game.".to_string());
+        }
+        if !self.mulligan_available || self.mulligan_used {
+            return Err("You have already used today's mulligan.".to_string());
+        }
+        if !self.undo() {
+            return Err("There is no guess to take back.".to_string());
+        }
+        self.mulligan_used = true;
+        Ok(())
+    }
+
+    /// The best status seen so far for every letter that has been guessed.
+    #[must_use]
+    pub fn keyboard_state(&self) -> &HashMap<char, LetterStatus> {
+        &self.keyboard_state
+    }
+
+    /// Whether `guess` is in either word list.
+    #[must_use]
+    pub fn is_valid_guess(&self, guess: &str) -> bool {
+        self.guessable.contains(guess)
+    }
+
+    /// The word in the game's word lists with the same length as `guess`
+    /// that is the fewest edits away from it, if it takes no more than
+    /// [`MAX_SUGGESTION_DISTANCE`]. Ties go to the word that sorts first.
+    #[must_use]
+    pub fn nearest(&self, guess: &str) -> Option<String> {
+        let length = guess.chars().count();
+        self.guessable
+            .iter()
+            .filter(|word| word.chars().count() == length)
+            .map(|word| (solver::edit_distance(guess, word), word))
+            .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+            .min()
+            .map(|(_, word)| word.clone())
+    }
+
+    /// Checks that raw input only has letters, and spaces around or between
+    /// them, before cleaning it up with [`sanitize`](Self::sanitize).
+    /// Otherwise something like `12345` would be sanitized down to nothing
+    /// and rejected for its length.
+    fn parse_guess(&self, input: &str) -> Result<String, InvalidGuess> {
+        if input
+            .chars()
+            .all(|c| c.is_whitespace() || self.charset.is_letter(c))
+        {
+            Ok(self.sanitize(input))
+        } else {
+            Err(InvalidGuess::NotLetters)
+        }
+    }
+
+    /// Whether `word` could be typed in and played, exactly as it is.
+    fn can_be_guessed(&self, word: &str) -> bool {
+        self.parse_guess(word).is_ok_and(|guess| guess == word) && self.check_word(word).is_ok()
+    }
+
+    /// Cleans up raw input the way the game's words were: as a phrase or
+    /// with [`Charset::sanitize`].
+    fn sanitize(&self, input: &str) -> String {
+        if self.phrases {
+            sanitize_phrase(input)
+        } else {
+            self.charset.sanitize(input)
+        }
+    }
+
+    /// Checks whether `guess`, already sanitized, may be played, without
+    /// recording it.
+    ///
+    /// # Errors
+    ///
+    /// Returns why the guess would be rejected: it has the wrong length,
+    /// isn't in the dictionary (unless any guess is allowed), repeats a
+    /// letter in an opening that must not, or breaks a hard mode rule.
+    pub fn validate(&self, guess: &str) -> Result<(), InvalidGuess> {
+        self.check_word(guess)?;
+        if self.distinct_first && self.guesses.is_empty() {
+            let mut seen = HashSet::new();
+            if !guess.chars().filter(|c| *c != ' ').all(|c| seen.insert(c)) {
+                return Err(InvalidGuess::RepeatedLetters);
+            }
+        }
+        match self.hard_mode_violation(guess) {
+            Some(violation) => Err(InvalidGuess::HardMode(violation)),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that `guess` has the word's shape and is in the dictionary,
+    /// unless any guess is allowed.
+    fn check_word(&self, guess: &str) -> Result<(), InvalidGuess> {
+        if self.phrases && !same_shape(guess, &self.word) {
+            return Err(InvalidGuess::WrongShape {
+                shape: shape_of(&self.word),
+            });
+        }
+        if guess.chars().count() != self.word_length {
+            return Err(InvalidGuess::WrongLength {
+                expected: self.word_length,
+            });
+        }
+        if !self.allow_any && !self.is_valid_guess(guess) {
+            return Err(InvalidGuess::NotInDictionary(guess.to_string()));
+        }
+        Ok(())
+    }
+
+    /// In hard mode, explains why `guess` ignores a hint revealed by an
+    /// earlier guess. Returns `None` if the guess is allowed.
+    #[must_use]
+    pub fn hard_mode_violation(&self, guess: &str) -> Option<String> {
+        if !self.hard_mode {
+            return None;
+        }
+
+        let letters: Vec<char> = guess.chars().collect();
+        let mut required: HashMap<char, usize> = HashMap::new();
+        for previous in &self.guesses {
+            let mut revealed: HashMap<char, usize> = HashMap::new();
+            for (pos, (c, status)) in previous.word.chars().zip(&previous.statuses).enumerate() {
+                if *status == LetterStatus::Correct && letters.get(pos) != Some(&c) {
+                    return Some(format!("Must use {c} in position {}", pos + 1));
+                }
+                if *status != LetterStatus::Absent {
+                    *revealed.entry(c).or_insert(0) += 1;
+                }
+            }
+            for (c, count) in revealed {
+                let needed = required.entry(c).or_insert(0);
+                *needed = (*needed).max(count);
+            }
+        }
+
+        let mut needed: Vec<(char, usize)> = required.into_iter().collect();
+        needed.sort_unstable();
+        needed
+            .into_iter()
+            .find(|(c, count)| letters.iter().filter(|l| *l == c).count() < *count)
+            .map(|(c, _)| format!("Guess must contain {c}"))
+    }
+
+    /// Renders the board in the game's render mode, one line per row. It
+    /// always has a numbered row for every try, with the ones not guessed
+    /// yet filled with [`EMPTY_CELL`], so it keeps its shape from turn to
+    /// turn. In zen mode only the next row is shown ahead, and when guesses
+    /// are described in words only the guesses are.
+    #[must_use]
+    pub fn render_board(&self) -> String {
+        let rows = if self.render_mode == RenderMode::Describe {
+            // Empty rows would only be noise for a screen reader.
+            self.guesses.len()
+        } else if self.zen {
+            self.guesses.len() + 1
+        } else {
+            self.max_tries.max(self.guesses.len())
+        };
+        let total = self.tries_label();
+        let mut board = String::new();
+        for row in 0..rows {
+            let cells = match self.guesses.get(row) {
+                Some(guess) if self.mode == Mode::Distance => {
+                    render_distance_row(&guess.word, self.distance(&guess.word))
+                }
+                Some(guess) => render_row(guess, self.render_mode, self.theme),
+                None => render_empty_row(&self.word),
+            };
+            let prefix = row_prefix(self.numbering, row, rows, &total);
+            let _ = writeln!(board, "{prefix}{cells}");
+        }
+        board
+    }
+
+    /// Prints every guess so far and the keyboard to stdout.
+    ///
+    /// # Panics
+    ///
+    /// Panics if writing to stdout fails.
+    pub fn display_guesses(&self) {
+        self.write_guesses(&mut io::stdout())
+            .expect("couldn't write to stdout");
+    }
+
+    /// Writes the board, followed by the keyboard unless hints are turned
+    /// off or guesses are described in words. With the assist or the coach
+    /// on, how many answers still fit the clues follows while the game is
+    /// going, except in distance mode, where it would give letters away.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `out` fails.
+    pub fn write_guesses(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "{}", self.render_board())?;
+        if self.coach && self.mode == Mode::Letters {
+            self.write_rating(out)?;
+        }
+        if self.shows_letters() && self.render_mode != RenderMode::Describe {
+            write!(
+                out,
+                "{}",
+                render_keyboard(
+                    &self.keyboard_state,
+                    &self.alphabet,
+                    self.render_mode,
+                    self.theme,
+                    terminal_width()
+                )
+            )?;
+        }
+        if (self.assist || self.coach)
+            && self.mode == Mode::Letters
+            && !self.guesses.is_empty()
+            && self.check_status() == GameStatus::InProgress
+        {
+            self.write_remaining_candidates(out)?;
+        }
+        Ok(())
+    }
+
+    /// Whether the keyboard and the letters not in the word are shown. They
+    /// would give away the letters in distance mode.
+    fn shows_letters(&self) -> bool {
+        self.hints_enabled && self.mode == Mode::Letters
+    }
+
+    /// Writes how many answers fit every guess so far, without naming them.
+    fn write_rating(&self, out: &mut impl Write) -> io::Result<()> {
+        let Some((rating, after, best_after)) = self.rate_last_guess() else {
+            return Ok(());
+        };
+        let words = |n: usize| if n == 1 { "word" } else { "words" };
+        writeln!(
+            out,
+            "{}",
+            format!(
+                "{} guess: it left {after} {}, the coach's pick would have left {best_after}.",
+                rating.name(),
+                words(after)
+            )
+            .dimmed()
+        )
+    }
+
+    fn write_remaining_candidates(&self, out: &mut impl Write) -> io::Result<()> {
+        let remaining = self.candidates().len();
+        let note = match remaining {
+            0 => "No word in the list fits the clues.".to_string(),
+            1 => format!("Only one word fits! Type {HINT_COMMAND} to see it."),
+            n => format!("{n} possible words remain."),
+        };
+        writeln!(out, "{}", note.dimmed())?;
+        if self.coach && remaining > 1 {
+            writeln!(
+                out,
+                "{}",
+                format!("Type {HINT_COMMAND} for the most informative next guess.").dimmed()
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_reused_letters(&self, guess: &str, out: &mut impl Write) -> io::Result<()> {
+        for c in self.reused_absent_letters(guess) {
+            writeln!(
+                out,
+                "{}",
+                format!("Note: you reused {c} which isn't in the word.").yellow()
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_letter_frequencies(&self, out: &mut impl Write) -> io::Result<()> {
+        let frequencies: Vec<String> = self
+            .letter_frequencies()
+            .iter()
+            .map(|(c, count)| format!("{c} {count}"))
+            .collect();
+        if frequencies.is_empty() {
+            writeln!(
+                out,
+                "{}",
+                "No unguessed letters are left in the candidates.".red()
+            )
+        } else {
+            writeln!(
+                out,
+                "{}",
+                format!("Candidates with each letter: {}", frequencies.join(", ")).cyan()
+            )
+        }
+    }
+
+    fn write_position_odds(&self, out: &mut impl Write) -> io::Result<()> {
+        let Some(positions) = self.position_odds() else {
+            return writeln!(
+                out,
+                "{}",
+                "Not enough information yet: make a guess first.".red()
+            );
+        };
+        if positions.is_empty() {
+            return writeln!(out, "{}", "No word fits the clues so far.".red());
+        }
+        for (pos, odds) in positions.iter().enumerate() {
+            if odds.is_empty() {
+                continue;
+            }
+            let likeliest: Vec<String> = odds
+                .iter()
+                .take(SHOWN_POSITION_LETTERS)
+                .map(|(c, odds)| format!("{c} {:.0}%", odds * 100.0))
+                .collect();
+            writeln!(
+                out,
+                "{}",
+                format!("{}: {}", pos + 1, likeliest.join(", ")).cyan()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Lists the letters known not to be in the word in alphabetical order,
+    /// so the line doesn't change order from one guess to the next. Sorting
+    /// the set itself also keeps letters outside the alphabet, which
+    /// `--allow-any` guesses can bring in.
+    fn write_invalid_letters(&self, out: &mut impl Write) -> io::Result<()> {
+        if self.guessed_letters.is_empty() {
+            return Ok(());
+        }
+        let mut letters: Vec<char> = self.guessed_letters.iter().copied().collect();
+        letters.sort_unstable();
+        let letters: Vec<String> = letters
+            .iter()
+            .map(|letter| {
+                let letter = letter.to_string();
+                if self.theme.dim_absent && self.render_mode.uses_color() {
+                    letter.strikethrough().dimmed().to_string()
+                } else {
+                    letter
+                }
+            })
+            .collect();
+        writeln!(out, "Letters not in the word: {}", letters.join(" "))
+    }
+
+    /// Prompts on stdin until a valid guess or a command is entered. Valid
+    /// guesses are recorded before they are returned. In a terminal the
+    /// line can be edited, and the up and down arrows recall earlier lines
+    /// of this game; Ctrl-C quits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if reading from stdin or writing to stdout fails.
+    pub fn ask_for_guess(&mut self) -> GuessAction {
+        let mut out = io::stdout();
+        self.write_prompt(&mut out)
+            .and_then(|()| self.read_action(|game, line| game.editor.read_line(line), &mut out))
+            .unwrap_or_else(|e| panic!("couldn't ask for a guess: {e}"))
+    }
+
+    /// Like [`ask_for_guess`](Self::ask_for_guess), but reads lines from
+    /// `input` and writes the prompt and any messages to `output`, so a game
+    /// can be driven by a script or a test. The end of the input counts as
+    /// quitting.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from `input` or writing to `output`
+    /// fails.
+    pub fn ask_for_guess_from(
+        &mut self,
+        mut input: impl BufRead,
+        mut output: impl Write,
+    ) -> io::Result<GuessAction> {
+        self.write_prompt(&mut output)?;
+        self.read_action(|_, line| input.read_line(line), &mut output)
+    }
+
+    fn write_prompt(&mut self, out: &mut impl Write) -> io::Result<()> {
+        let started = *self.started.get_or_insert_with(Instant::now);
+        if self.timed {
+            writeln!(
+                out,
+                "{}",
+                format!("Time: {}", format_duration(started.elapsed())).dimmed()
+            )?;
+        }
+        if self.remaining_guesses() == 1 {
+            writeln!(out, "{}", "Last guess!".bright_magenta().bold())?;
+        } else {
+            writeln!(
+                out,
+                "{}",
+                format!(
+                    "Guess {} of {}.",
+                    self.numbering.number(self.guesses.len()),
+                    self.tries_label()
+                )
+                .cyan()
+            )?;
+        }
+        if self.legend && self.guesses.is_empty() && self.mode == Mode::Letters {
+            if let Some(legend) = render_legend(self.render_mode, self.theme) {
+                writeln!(out, "{legend}")?;
+            }
+        }
+        writeln!(
+            out,
+            "{}",
+            if self.phrases {
+                format!(
+                    "Enter your guess, shaped like {}, and press ENTER",
+                    shape_of(&self.word)
+                )
+            } else {
+                format!(
+                    "Enter your word guess ({} letters) and press ENTER",
+                    self.word_length
+                )
+            }
+            .cyan()
+        )?;
+        if self.shows_letters() {
+            self.write_invalid_letters(out)?;
+        }
+        out.flush()
+    }
+
+    /// Reads lines with `read_line` until one is a valid guess or a command
+    /// that ends the game, writing any messages to `out`. The end of the
+    /// input, or Ctrl-C, counts as quitting.
+    fn read_action(
+        &mut self,
+        mut read_line: impl FnMut(&mut Self, &mut String) -> io::Result<usize>,
+        out: &mut impl Write,
+    ) -> io::Result<GuessAction> {
+        loop {
+            let mut line = String::new();
+            match read_line(self, &mut line) {
+                Ok(0) => return Ok(GuessAction::Quit),
+                Ok(_) => {}
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => return Ok(GuessAction::Quit),
+                Err(e) => return Err(e),
+            }
+            match line.trim() {
+                REVEAL_COMMAND => return Ok(GuessAction::Reveal),
+                QUIT_COMMAND => return Ok(GuessAction::Quit),
+                UNDO_COMMAND if self.daily => {
+                    writeln!(
+                        out,
+                        "{}",
+                        format!("The daily game can't be undone; try {MULLIGAN_COMMAND}.").red()
+                    )?;
+                    continue;
+                }
+                UNDO_COMMAND => {
+                    if self.undo() {
+                        self.write_guesses(out)?;
+                    } else {
+                        writeln!(out, "{}", "There is no guess to undo.".red())?;
+                    }
+                    continue;
+                }
+                MULLIGAN_COMMAND => {
+                    match self.mulligan() {
+                        Ok(()) => self.write_guesses(out)?,
+                        Err(reason) => writeln!(out, "{}", reason.red())?,
+                    }
+                    continue;
+                }
+                HINT_COMMAND | PEEK_COMMAND | FREQ_COMMAND | POSITIONS_COMMAND
+                    if self.mode == Mode::Distance =>
+                {
+                    writeln!(
+                        out,
+                        "{}",
+                        format!("{} would give letters away in distance mode.", line.trim()).red()
+                    )?;
+                    continue;
+                }
+                PEEK_COMMAND => {
+                    match self.peek() {
+                        Ok(_) => self.write_guesses(out)?,
+                        Err(reason) => writeln!(out, "{}", reason.red())?,
+                    }
+                    continue;
+                }
+                HINT_COMMAND => {
+                    match self.suggest() {
+                        Some(word) if self.coach => {
+                            writeln!(out, "{}", format!("The coach would try {word}.").dimmed())?;
+                        }
+                        Some(word) => writeln!(out, "{}", format!("Try {word}").cyan())?,
+                        None => writeln!(out, "{}", "No word fits the clues so far.".red())?,
+                    }
+                    continue;
+                }
+                FREQ_COMMAND if self.frequency_hints => {
+                    self.write_letter_frequencies(out)?;
+                    continue;
+                }
+                POSITIONS_COMMAND if self.position_hints => {
+                    self.write_position_odds(out)?;
+                    continue;
+                }
+                _ => {}
+            }
+
+            match self
+                .parse_guess(&line)
+                .and_then(|guess| self.validate(&guess).map(|()| guess))
+            {
+                Ok(guess) => {
+                    if !self.is_valid_guess(&guess) {
+                        writeln!(
+                            out,
+                            "{}",
+                            format!("Note: {guess} isn't in the dictionary.").dimmed()
+                        )?;
+                    }
+                    if self.assist && self.mode == Mode::Letters {
+                        self.write_reused_letters(&guess, out)?;
+                    }
+                    self.guess(&guess);
+                    return Ok(GuessAction::Word(guess));
+                }
+                Err(invalid) => {
+                    let mut message = invalid.to_string();
+                    if let InvalidGuess::NotInDictionary(guess) = &invalid {
+                        if let Some(word) = self.nearest(guess) {
+                            let _ = write!(message, " Did you mean {word}?");
+                        }
+                    }
+                    writeln!(out, "{}", message.red())?;
+                }
+            }
+        }
+    }
+
+    /// The result as a grid of colored squares, one row per guess, under a
+    /// `Rustle 3/6` header (`X/6` if the word wasn't found). It doesn't
+    /// reveal the word, so it can be shared.
+    #[must_use]
+    pub fn share_grid(&self) -> String {
+        let score = match self.check_status() {
+            GameStatus::Won { tries } => tries.to_string(),
+            GameStatus::Lost { .. } | GameStatus::InProgress => "X".to_string(),
+        };
+        let mut grid = format!("Rustle {score}/{}", self.tries_label());
+        for guess in &self.guesses {
+            grid.push('\n');
+            match self.mode {
+                Mode::Letters => grid.push_str(&emoji_row(&guess.statuses)),
+                Mode::Distance => grid.push_str(&self.distance(&guess.word).to_string()),
+            }
+        }
+        grid
+    }
+
+    /// A commitment to the result of the game played on `date` (in days
+    /// since the Unix epoch): a hash of the date, the word, and every guess
+    /// with its statuses. Anyone who knows the word can recompute it to check
+    /// a shared result, but it doesn't give the word away. The same inputs
+    /// always give the same hash.
+    #[must_use]
+    pub fn result_hash(&self, date: u64) -> String {
+        let mut hash = Fnv1a::default();
+        hash.write(&date.to_le_bytes());
+        hash.write(self.word.as_bytes());
+        for guess in &self.guesses {
+            // Separators keep e.g. `AB` + `C` apart from `A` + `BC`.
+            hash.write(&[0]);
+            hash.write(guess.word.as_bytes());
+            hash.write(&[0]);
+            hash.write(&guess.statuses.iter().map(|s| s.rank()).collect::<Vec<_>>());
+        }
+        format!("{:016x}", hash.0)
+    }
+
+    /// Whether the game has been won or lost, judging by the guesses so far.
+    /// A win is checked first, so finding the word with the last try wins.
+    #[must_use]
+    pub fn check_status(&self) -> GameStatus {
+        let n_tries = self.guesses.len();
+        if self
+            .guesses
+            .last()
+            .is_some_and(|guess| guess.word == self.word)
+        {
+            GameStatus::Won { tries: n_tries }
+        } else if !self.zen && n_tries >= self.max_tries {
+            GameStatus::Lost {
+                word: self.word.clone(),
+            }
+        } else {
+            GameStatus::InProgress
+        }
+    }
+}
+
+/// The 64-bit FNV-1a hash, which unlike [`std::hash::DefaultHasher`] is
+/// guaranteed to stay the same between Rust versions and runs.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+/// Scores `guess` against `word`, letter by letter. Letters are marked
+/// correct first, and then misplaced only while `word` still has unmatched
+/// copies of them.
+#[must_use]
+pub fn evaluate(word: &str, guess: &str) -> Vec<LetterStatus> {
+    let word_chars: Vec<char> = word.chars().collect();
+    let mut statuses = vec![LetterStatus::Absent; guess.chars().count()];
+
+    let mut char_count: HashMap<char, i32> = create_charmap(word);
+
+    // Mark the correct letters
+    guess.chars().enumerate().for_each(|(pos, c)| {
+        if word_chars.get(pos) == Some(&c) {
+            statuses[pos] = LetterStatus::Correct;
+            char_count.entry(c).and_modify(|e| *e -= 1);
+        }
+    });
+
+    // Mark the misplaced letters, but only as many as are left unmatched in
+    // the word
+    guess.chars().enumerate().for_each(|(pos, c)| {
+        if statuses[pos] == LetterStatus::Correct {
+            return;
+        }
+
+        if let Some(count) = char_count.get_mut(&c) {
+            if *count > 0 {
+                statuses[pos] = LetterStatus::Misplaced;
+                *count -= 1;
+            }
+        }
+    });
+
+    statuses
+}
+
+/// The longest pattern that fits in a [`pattern_code`]: 3^20 is just under
+/// `u32::MAX`.
+pub const MAX_PATTERN_LENGTH: usize = 20;
+
+/// Packs how a guess scored into a single number in base 3, one digit per
+/// letter with the first letter the most significant: `0` for absent, `1`
+/// for misplaced and `2` for correct. Every pattern of a given length gets
+/// its own code, so it can key tables of patterns, e.g. in the solver.
+///
+/// # Panics
+///
+/// Panics if there are more than [`MAX_PATTERN_LENGTH`] statuses.
+#[must_use]
+pub fn pattern_code(statuses: &[LetterStatus]) -> u32 {
+    assert!(
+        statuses.len() <= MAX_PATTERN_LENGTH,
+        "a pattern of {} letters doesn't fit in a u32",
+        statuses.len()
+    );
+    statuses
+        .iter()
+        .fold(0, |code, status| code * 3 + u32::from(status.rank()))
+}
+
+/// Unpacks a [`pattern_code`] into the statuses of a `length` letter guess.
+#[must_use]
+pub fn decode_pattern(mut code: u32, length: usize) -> Vec<LetterStatus> {
+    let mut statuses = vec![LetterStatus::Absent; length];
+    for status in statuses.iter_mut().rev() {
+        *status = LetterStatus::from_rank(code % 3);
+        code /= 3;
+    }
+    statuses
+}
+
+/// Formats a game's duration as minutes and seconds, e.g. `1:05.3`.
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!(
+        "{}:{:02}.{}",
+        seconds / 60,
+        seconds % 60,
+        duration.subsec_millis() / 100
+    )
+}
+
+/// Picks the word of the day for `day` (counted in days since the Unix epoch).
+/// The same dictionary and day always give the same word.
+///
+/// # Panics
+///
+/// Panics if `dictionary` is empty.
+#[must_use]
+pub fn daily_word(dictionary: &[String], day: u64) -> String {
+    let mut rng = RandomNumberGenerator::seeded(day);
+    rng.random_slice_entry(dictionary).unwrap().clone()
+}
+
+/// Today's day number, counted in days since the Unix epoch (UTC), as used
+/// by [`daily_word`].
+///
+/// # Panics
+///
+/// Panics if the system clock is set before the Unix epoch.
+#[must_use]
+pub fn today() -> u64 {
+    let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    since_epoch.as_secs() / SECONDS_PER_DAY
+}
+
+/// Counts how many times each character occurs in `word`.
+#[must_use]
+pub fn create_charmap(word: &str) -> HashMap<char, i32> {
+    let mut charmap: HashMap<char, i32> = HashMap::new();
+    word.chars().for_each(|c| {
+        let count = charmap.entry(c).or_insert(0);
+        *count += 1;
+    });
+    charmap
+}
+
+/// Whether `c` is rendered in the foreground color `col`, whatever other
+/// styling it has.
+#[must_use]
+pub fn is_color(c: &ColoredString, col: Color) -> bool {
+    c.fgcolor() == Some(col)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CORRECT_COLOR: Color = Theme::CLASSIC.correct;
+    const MISPLACED_COLOR: Color = Theme::CLASSIC.misplaced;
+    const INCORRECT_COLOR: Color = Theme::CLASSIC.absent;
+
+    #[test]
+    fn test_sanitize_word() {
+        assert_eq!(sanitize_word("hello"), "HELLO");
+        assert_eq!(sanitize_word("hello world"), "HELLOWORLD");
+    }
+
+    #[test]
+    fn test_words_list_is_clean() {
+        let words = words_list(WORD_LENGTH);
+        let unique: HashSet<&String> = words.iter().collect();
+        assert_eq!(unique.len(), words.len());
+        assert!(words
+            .iter()
+            .all(|word| word.len() == WORD_LENGTH && word.chars().all(|c| c.is_ascii_uppercase())));
+        assert_eq!(words.first().map(String::as_str), Some("AAHED"));
+    }
+
+    #[test]
+    fn test_filter_words_handles_crlf_and_duplicates() {
+        let text = "Word List 2024\r\n\r\ncrane\r\nslate\r\nCRANE\r\n";
+        assert_eq!(
+            filter_words(skip_header(text.lines()), 5, Charset::Ascii),
+            vec!["CRANE", "SLATE"]
+        );
+        assert_eq!(
+            filter_words(skip_header("crane\nslate".lines()), 5, Charset::Ascii),
+            vec!["CRANE", "SLATE"]
+        );
+    }
+
+    #[test]
+    fn test_headerless_list_keeps_every_word() {
+        let text = "aahed\naalii\nabaca\nabaci\n";
+        assert_eq!(
+            filter_words(skip_header(text.lines()), 5, Charset::Ascii),
+            vec!["AAHED", "AALII", "ABACA", "ABACI"]
+        );
+        assert_eq!(dictionary_words(text, Charset::Ascii).len(), 4);
+    }
+
+    #[test]
+    fn test_embedded_header_is_skipped() {
+        let header: Vec<&str> = ALL_WORDS
+            .lines()
+            .take_while(|line| skip_header(std::iter::once(*line)).next().is_none())
+            .collect();
+        assert_eq!(
+            header,
+            ["TWL06 Scrabble Word List", ""],
+            "words.txt no longer starts with its title and a blank line"
+        );
+        let first = skip_header(ALL_WORDS.lines()).next();
+        assert_eq!(first, Some("aa"), "the first word of words.txt was skipped");
+    }
+
+    #[test]
+    fn test_blocked_words_are_never_played() {
+        let entries = |words: &[&str]| -> Vec<DictionaryEntry> {
+            words
+                .iter()
+                .map(|word| DictionaryEntry {
+                    word: (*word).to_string(),
+                    frequency: None,
+                })
+                .collect()
+        };
+        let blocklist = HashSet::from(["BLOCK".to_string()]);
+        let mut answers = entries(&["CRANE", "BLOCK", "SLATE"]);
+        remove_blocked(&mut answers, &blocklist);
+        assert_eq!(into_words(answers.clone()), vec!["CRANE", "SLATE"]);
+
+        let mut guesses = entries(&["CRANE", "BLOCK", "SLATE", "GRACE"]);
+        for seed in 0..50 {
+            let game = RustleGame::builder()
+                .answers(into_words(answers.clone()))
+                .dictionary(into_words(guesses.clone()))
+                .seed(seed)
+                .build()
+                .unwrap();
+            assert_ne!(game.word(), "BLOCK");
+        }
+
+        remove_blocked(&mut guesses, &blocklist);
+        let mut game = RustleGame::builder()
+            .answers(into_words(answers))
+            .dictionary(into_words(guesses))
+            .build()
+            .unwrap();
+        assert_eq!(
+            game.submit("block"),
+            Err(InvalidGuess::NotInDictionary("BLOCK".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_keep_most_common() {
+        let text = "crane 900\nslate 500\nxylyl 3\nfjord\ngrace 700\n";
+        let entries = filter_entries(text.lines(), 5, Charset::Ascii);
+
+        let mut top = entries.clone();
+        keep_most_common(&mut top, 3);
+        assert_eq!(top.len(), 3);
+        assert_eq!(into_words(top), vec!["CRANE", "SLATE", "GRACE"]);
+
+        let mut all = entries.clone();
+        keep_most_common(&mut all, 10);
+        assert_eq!(all, entries);
+
+        let mut unranked = filter_entries("crane\nslate\ngrace\n".lines(), 5, Charset::Ascii);
+        keep_most_common(&mut unranked, 1);
+        assert_eq!(unranked.len(), 3);
+    }
+
+    #[test]
+    fn test_frequency_column() {
+        let text = "Common words\ncrane 900\nslate,500\nxylyl\t3\nfjord\n";
+        assert_eq!(
+            filter_entries(skip_header(text.lines()), 5, Charset::Ascii),
+            vec![
+                DictionaryEntry {
+                    word: "CRANE".to_string(),
+                    frequency: Some(900)
+                },
+                DictionaryEntry {
+                    word: "SLATE".to_string(),
+                    frequency: Some(500)
+                },
+                DictionaryEntry {
+                    word: "XYLYL".to_string(),
+                    frequency: Some(3)
+                },
+                DictionaryEntry {
+                    word: "FJORD".to_string(),
+                    frequency: None
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_difficulty_answers() {
+        let entries = filter_entries(
+            "crane 900\nslate 500\nadieu 40\nxylyl 3\nfjord".lines(),
+            5,
+            Charset::Ascii,
+        );
+        assert_eq!(Difficulty::Easy.answers(&entries), vec!["CRANE"]);
+        assert_eq!(Difficulty::Medium.answers(&entries), vec!["CRANE", "SLATE"]);
+        assert_eq!(Difficulty::Hard.answers(&entries).len(), 5);
+
+        let plain = filter_entries("crane\nslate".lines(), 5, Charset::Ascii);
+        assert_eq!(Difficulty::Easy.answers(&plain), vec!["CRANE", "SLATE"]);
+    }
+
+    #[test]
+    fn test_is_color() {
+        let c = "a".bright_green();
+        assert!(is_color(&c, Color::BrightGreen));
+        assert!(!is_color(&c, Color::Blue));
+        assert!(!is_color(&"a".normal(), Color::BrightGreen));
+
+        let styled = "a".bright_green().bold().underline();
+        assert!(is_color(&styled, Color::BrightGreen));
+        assert!(!is_color(&styled, Color::Green));
+    }
+
+    #[test]
+    fn test_create_charmap() {
+        let word = "hello";
+        let charmap = create_charmap(word);
+        assert_eq!(charmap[&'h'], 1);
+        assert_eq!(charmap[&'e'], 1);
+        assert_eq!(charmap[&'l'], 2);
+        assert_eq!(charmap[&'o'], 1);
+    }
+
+    #[test]
+    fn test_colorize_guess_all_correct() {
+        let mut game = RustleGame::new().unwrap();
+        game.word = "ABC".to_string();
+        let colored_guess = game.colorize_guess("ABC");
+        assert_eq!(
+            colored_guess,
+            (vec![
+                "A".color(CORRECT_COLOR),
+                "B".color(CORRECT_COLOR),
+                "C".color(CORRECT_COLOR)
+            ])
+        );
+        assert_eq!(game.guessed_letters, (vec![]).into_iter().collect());
+    }
+
+    #[test]
+    fn test_colorize_guess_all_incorrect() {
+        let mut game = RustleGame::new().unwrap();
+        game.word = "ABC".to_string();
+        let colored_guess = game.colorize_guess("DEF");
+        assert_eq!(
+            colored_guess,
+            (vec![
+                "D".color(INCORRECT_COLOR),
+                "E".color(INCORRECT_COLOR),
+                "F".color(INCORRECT_COLOR)
+            ])
+        );
+        assert_eq!(
+            game.guessed_letters,
+            (vec!['D', 'E', 'F'].into_iter().collect())
+        );
+    }
+
+    #[test]
+    fn test_colorize_guess_two_misplaced() {
+        let mut game = RustleGame::new().unwrap();
+        game.word = "ABC".to_string();
+        let colored_guess = game.colorize_guess("ACB");
+        assert_eq!(
+            colored_guess,
+            (vec![
+                "A".color(CORRECT_COLOR),
+                "C".color(MISPLACED_COLOR),
+                "B".color(MISPLACED_COLOR)
+            ])
+        );
+        assert_eq!(game.guessed_letters, (vec![]).into_iter().collect());
+    }
+
+    #[test]
+    fn test_colorize_guess_one_correct_one_misplaced() {
+        let mut game = RustleGame::new().unwrap();
+        game.word = "ABC".to_string();
+        let colored_guess = game.colorize_guess("ACD");
+        assert_eq!(
+            colored_guess,
+            (vec![
+                "A".color(CORRECT_COLOR),
+                "C".color(MISPLACED_COLOR),
+                "D".color(INCORRECT_COLOR)
+            ])
+        );
+        assert_eq!(game.guessed_letters, (vec!['D'].into_iter().collect()));
+    }
+
+    #[test]
+    fn test_evaluate_guess() {
+        let mut game = RustleGame::new().unwrap();
+        game.word = "ABC".to_string();
+        assert_eq!(
+            game.evaluate_guess("ACD"),
+            vec![
+                LetterStatus::Correct,
+                LetterStatus::Misplaced,
+                LetterStatus::Absent
+            ]
+        );
+        assert!(game.guessed_letters.is_empty());
+    }
+
+    #[test]
+    fn test_evaluate_guess_duplicate_letters() {
+        use LetterStatus::{Absent, Correct, Misplaced};
+        let mut game = RustleGame::new().unwrap();
+
+        game.word = "ALLEY".to_string();
+        assert_eq!(
+            game.evaluate_guess("LLAMA"),
+            vec![Misplaced, Correct, Misplaced, Absent, Absent]
+        );
+
+        game.word = "SPEED".to_string();
+        assert_eq!(
+            game.evaluate_guess("ERASE"),
+            vec![Misplaced, Absent, Absent, Misplaced, Misplaced]
+        );
+
+        game.word = "ABBEY".to_string();
+        assert_eq!(
+            game.evaluate_guess("BUBBA"),
+            vec![Misplaced, Absent, Correct, Absent, Misplaced]
+        );
+    }
+
+    #[test]
+    fn test_evaluate_never_marks_more_letters_than_the_word_has() {
+        // Random words over a small alphabet, so most have repeated letters.
+        fn random_word(rng: &mut RandomNumberGenerator, length: usize) -> String {
+            (0..length)
+                .map(|_| ['A', 'B', 'C', 'D'][rng.range(0, 4)])
+                .collect()
+        }
+        let mut rng = RandomNumberGenerator::seeded(85);
+        for _ in 0..10_000 {
+            let length = rng.range(1, 8);
+            let word = random_word(&mut rng, length);
+            let guess = random_word(&mut rng, length);
+            let statuses = evaluate(&word, &guess);
+            for letter in guess.chars() {
+                let marked = guess
+                    .chars()
+                    .zip(&statuses)
+                    .filter(|&(c, status)| c == letter && *status != LetterStatus::Absent)
+                    .count();
+                let available = word.chars().filter(|&c| c == letter).count();
+                assert!(marked <= available, "{guess} against {word}: {statuses:?}");
+            }
+            for ((w, g), status) in word.chars().zip(guess.chars()).zip(&statuses) {
+                assert_eq!(
+                    w == g,
+                    *status == LetterStatus::Correct,
+                    "{guess} against {word}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_matching() {
+        let game = RustleGame::from_word_lists(
+            vec!["CRANE".into(), "CRONY".into()],
+            vec![
+                "CRANE".into(),
+                "CRONE".into(),
+                "CRONY".into(),
+                "SLATE".into(),
+            ],
+            5,
+        )
+        .unwrap();
+        assert_eq!(game.matching("CR.N."), vec!["CRANE", "CRONE", "CRONY"]);
+        assert_eq!(game.matching(".....").len(), 4);
+    }
+
+    #[test]
+    fn test_candidates_and_suggest() {
+        let words: Vec<String> = ["CRANE", "CANES", "CHIRP", "STORY"]
+            .map(String::from)
+            .into();
+        let mut game = RustleGame::from_word_lists(words.clone(), words, 5).unwrap();
+        game.word = "CRANE".to_string();
+        assert_eq!(game.candidates().len(), 4);
+
+        game.guess("CLEAN");
+        assert_eq!(game.candidates(), vec!["CRANE", "CANES"]);
+        assert!(game
+            .suggest()
+            .is_some_and(|word| word == "CRANE" || word == "CANES"));
+    }
+
+    #[test]
+    fn test_with_length() {
+        let game = RustleGame::with_length(4).unwrap();
+        assert_eq!(game.word_length(), 4);
+        assert_eq!(game.word.len(), 4);
+        assert!(game.answers.iter().all(|word| word.len() == 4));
+    }
+
+    #[test]
+    fn test_end_of_input_quits() {
+        let mut game = RustleGame::replay_of("CRANE".to_string(), vec!["CRANE".to_string()]);
+        let input = io::Cursor::new("not a word\n");
+        let action = game.ask_for_guess_from(input, io::sink()).unwrap();
+        assert_eq!(action, GuessAction::Quit);
+        assert!(game.guesses().is_empty());
+    }
+
+    #[test]
+    fn test_remaining_guesses() {
+        let mut game = RustleGame::new().unwrap().with_max_tries(3);
+        game.word = "CRANE".to_string();
+        assert_eq!(game.remaining_guesses(), 3);
+        game.guess("SLATE");
+        game.guess("BRAVE");
+        assert_eq!(game.remaining_guesses(), 1);
+        game.guess("GRACE");
+        assert_eq!(game.remaining_guesses(), 0);
+    }
+
+    #[test]
+    fn test_max_tries() {
+        let mut game = RustleGame::new().unwrap().with_max_tries(2);
+        game.word = "ABCDE".to_string();
+        assert_eq!(game.max_tries(), 2);
+        game.guess("FGHIJ");
+        assert_eq!(game.check_status(), GameStatus::InProgress);
+        game.guess("FGHIJ");
+        assert_eq!(
+            game.check_status(),
+            GameStatus::Lost {
+                word: "ABCDE".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_zen_mode() {
+        let mut game = RustleGame::new().unwrap().with_max_tries(2).with_zen(true);
+        game.word = "CRANE".to_string();
+        for _ in 0..10 {
+            game.guess("SLATE");
+        }
+        assert_eq!(game.check_status(), GameStatus::InProgress);
+        assert_eq!(game.remaining_guesses(), usize::MAX);
+        assert_eq!(game.render_board().lines().count(), 11);
+        game.guess("CRANE");
+        assert_eq!(game.check_status(), GameStatus::Won { tries: 11 });
+        assert!(game.share_grid().starts_with("Rustle 11/∞\n"));
+        assert!(game.replay().zen());
+    }
+
+    #[test]
+    fn test_check_status_win() {
+        let mut game = RustleGame::new().unwrap();
+        game.word = "ABCDE".to_string();
+        assert_eq!(game.check_status(), GameStatus::InProgress);
+        game.guess("FGHIJ");
+        game.guess("ABCDE");
+        assert_eq!(game.check_status(), GameStatus::Won { tries: 2 });
+    }
+
+    #[test]
+    fn test_elapsed_starts_with_first_guess() {
+        let mut game = RustleGame::new().unwrap();
+        assert_eq!(game.elapsed(), None);
+        game.guess("CRANE");
+        assert!(game.elapsed().is_some());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(65_300)), "1:05.3");
+        assert_eq!(format_duration(Duration::from_millis(999)), "0:00.9");
+    }
+
+    #[test]
+    fn test_distance_mode() {
+        let mut game = RustleGame::new()
+            .unwrap()
+            .with_max_tries(2)
+            .with_mode(Mode::Distance);
+        game.word = "CRANE".to_string();
+        game.guess("CRATE");
+        assert_eq!(game.distance("CRATE"), 1);
+        let board = game.render_board();
+        assert!(board.starts_with(&format!("1: {}\n", render_distance_row("CRATE", 1))));
+
+        let mut out = Vec::new();
+        game.write_guesses(&mut out).unwrap();
+        game.write_prompt(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(!out.contains("Letters not in the word"));
+        assert!(!out.contains("Q  W  E"));
+
+        game.guess("CRANE");
+        assert_eq!(game.check_status(), GameStatus::Won { tries: 2 });
+        assert_eq!(game.share_grid(), "Rustle 2/2\n1\n0");
+    }
+
+    #[test]
+    fn test_distance_mode_gives_no_letters_away() {
+        let dictionary: Vec<String> = ["CRANE", "GRACE", "BRAVE", "SLOTH"]
+            .map(String::from)
+            .into();
+        let mut game = RustleGame::replay_of("CRANE".to_string(), dictionary)
+            .with_mode(Mode::Distance)
+            .with_assist(true)
+            .with_coach(true)
+            .with_peeks(true)
+            .with_position_hints(true)
+            .with_frequency_hints(true);
+        game.guess("SLOTH");
+        let mut output = Vec::new();
+        game.ask_for_guess_from("sloth\n".as_bytes(), &mut output)
+            .unwrap();
+        for command in [HINT_COMMAND, PEEK_COMMAND, FREQ_COMMAND, POSITIONS_COMMAND] {
+            game.ask_for_guess_from(format!("{command}\n").as_bytes(), &mut output)
+                .unwrap();
+        }
+        game.write_guesses(&mut output).unwrap();
+        assert_eq!(game.guesses().len(), 2);
+        assert_eq!(game.peeks_used(), 0);
+        let output = String::from_utf8(output).unwrap();
+        for leak in ["reused", "remain", "fits", "Try", "coach", "%"] {
+            assert!(!output.contains(leak), "{leak} in {output}");
+        }
+        assert!(output.contains("/hint would give letters away in distance mode."));
+    }
+
+    #[test]
+    fn test_render_board() {
+        let mut game = RustleGame::new()
+            .unwrap()
+            .with_max_tries(3)
+            .with_render_mode(RenderMode::Symbols);
+        game.word = "CRANE".to_string();
+        let empty = render_empty_row("CRANE");
+        assert_eq!(
+            game.render_board(),
+            format!("1: {empty}\n2: {empty}\n3: {empty}\n")
+        );
+        game.guess("CLEAN");
+        assert_eq!(
+            game.render_board(),
+            format!("1: [C] L (E)(A)(N)\n2: {empty}\n3: {empty}\n")
+        );
+        assert!(!game.render_board().contains('\x1b'));
+
+        let game = game.with_numbering(Numbering::OfTotal);
+        assert!(game
+            .render_board()
+            .starts_with("Guess 1/3: [C] L (E)(A)(N)\nGuess 2/3: "));
+
+        let game = game.with_render_mode(RenderMode::Describe);
+        assert_eq!(
+            game.render_board(),
+            "Guess 1/3: C correct, L absent, E misplaced, A misplaced, N misplaced.\n"
+        );
+    }
+
+    #[test]
+    fn test_share_grid() {
+        let mut game = RustleGame::new().unwrap();
+        game.word = "CRANE".to_string();
+        game.guess("CLEAN");
+        assert_eq!(game.share_grid(), "Rustle X/6\n🟩⬛🟨🟨🟨");
+        game.guess("CRANE");
+        assert_eq!(game.share_grid(), "Rustle 2/6\n🟩⬛🟨🟨🟨\n🟩🟩🟩🟩🟩");
+    }
+
+    #[test]
+    fn test_result_hash() {
+        let mut game = RustleGame::new().unwrap();
+        game.word = "CRANE".to_string();
+        game.guess("CLEAN");
+        game.guess("CRANE");
+        let hash = game.result_hash(19_000);
+        assert_eq!(hash, "d7308fa7066a1a95");
+        assert_eq!(hash, game.result_hash(19_000));
+        assert_eq!(hash.len(), 16);
+        assert!(!hash.to_uppercase().contains("CRANE"));
+        assert_ne!(hash, game.result_hash(19_001));
+
+        let mut other = game.replay();
+        other.guess("SLATE");
+        other.guess("CRANE");
+        assert_ne!(hash, other.result_hash(19_000));
+    }
+
+    #[test]
+    fn test_daily_word_is_stable() {
+        let dictionary = words_list(WORD_LENGTH);
+        let word = daily_word(&dictionary, 19_000);
+        assert_eq!(daily_word(&dictionary, 19_000), word);
+        assert!(dictionary.contains(&word));
+    }
+
+    #[test]
+    fn test_from_dictionary_file() {
+        let dir = std::env::temp_dir().join(format!("rustle-dict-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("words.txt");
+
+        fs::write(&path, "ox\nfour\n  Crab \nswift\n").unwrap();
+        let game = RustleGame::from_dictionary_file(&path, 4, Charset::Ascii).unwrap();
+        assert_eq!(game.answers, vec!["FOUR", "CRAB"]);
+        assert!(game.answers.contains(&game.word));
+
+        assert!(matches!(
+            RustleGame::from_dictionary_file(&path, 3, Charset::Ascii),
+            Err(GameError::EmptyDictionary { word_length: 3 })
+        ));
+        assert!(matches!(
+            RustleGame::from_dictionary_file(dir.join("missing.txt"), 4, Charset::Ascii),
+            Err(GameError::IoError(_))
+        ));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_words_strict() {
+        assert_eq!(
+            load_words_strict("crane\n\nSlate 120\n", 5),
+            Ok(vec!["CRANE".to_string(), "SLATE".to_string()])
+        );
+        assert_eq!(
+            load_words_strict("crane\ncranes\nbr4ve\ntrace\nit's\n", 5),
+            Err(vec![
+                (2, "cranes".to_string()),
+                (3, "br4ve".to_string()),
+                (5, "it's".to_string()),
+            ])
+        );
+        assert!(load_words_strict("crème", 5).is_err());
+        assert!(check_dictionary_entries("crème", 5, Charset::Unicode).is_ok());
+    }
+
+    #[test]
+    fn test_read_dictionary() {
+        let input = "  crane\r\nSLATE\n\ncrane \nox\nCrAnE\n";
+        let words = read_dictionary(input.as_bytes(), 5, Charset::Ascii).unwrap();
+        assert_eq!(words, vec!["CRANE", "SLATE"]);
+        assert!(matches!(
+            read_dictionary("ox\n".as_bytes(), 5, Charset::Ascii),
+            Err(GameError::EmptyDictionary { word_length: 5 })
+        ));
+    }
+
+    #[test]
+    fn test_unicode_dictionary() {
+        assert_eq!(sanitize_word_unicode(" niño\r"), "NIÑO");
+        assert_eq!(sanitize_word("niño"), "NIO");
+
+        let dir = std::env::temp_dir().join(format!("rustle-unicode-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("palabras.txt");
+        fs::write(&path, "niño\ncafé\npingüino\n").unwrap();
+
+        let game = RustleGame::from_dictionary_file(&path, 4, Charset::Unicode).unwrap();
+        assert_eq!(game.answers, vec!["NIÑO", "CAFÉ"]);
+        assert!(RustleGame::from_dictionary_file(&path, 4, Charset::Ascii).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_alphabet_of_accented_dictionary() {
+        let words = read_dictionary(
+            "árbol\nniño\npingüino\ncañón\n".as_bytes(),
+            5,
+            Charset::Unicode,
+        )
+        .unwrap();
+        assert_eq!(
+            alphabet_of(&words),
+            vec!['A', 'B', 'C', 'L', 'N', 'O', 'R', 'Á', 'Ñ', 'Ó']
+        );
+
+        let game = RustleGame::from_word_lists(words.clone(), words, 5).unwrap();
+        assert_eq!(game.alphabet().len(), 10);
+        assert_eq!(RustleGame::new().unwrap().alphabet().len(), 26);
+    }
+
+    #[test]
+    fn test_separate_answer_and_guess_lists() {
+        let answers = vec!["CRANE".to_string()];
+        let guesses = vec![
+            "CRANE".to_string(),
+            "SLATE".to_string(),
+            "TRACE".to_string(),
+        ];
+        let game = RustleGame::from_word_lists(answers, guesses, 5).unwrap();
+        assert_eq!(game.word(), "CRANE");
+        assert!(game.is_valid_guess("CRANE"));
+        assert!(game.is_valid_guess("SLATE"));
+        assert!(!game.is_valid_guess("ADIEU"));
+    }
+
+    #[test]
+    fn test_valid_guesses_match_the_word_lists() {
+        let answers = words_list(WORD_LENGTH);
+        let mut valid_guesses = answers.clone();
+        valid_guesses.extend(["ZZZZZ".to_string(), "QQQQQ".to_string()]);
+        let game = RustleGame::from_word_lists(answers.clone(), valid_guesses.clone(), 5).unwrap();
+        for word in answers.iter().chain(&valid_guesses) {
+            assert!(game.is_valid_guess(word));
+        }
+        for word in ["CRAN", "CRANES", "crane", "XXXXX", ""] {
+            let listed = answers.iter().chain(&valid_guesses).any(|w| w == word);
+            assert_eq!(game.is_valid_guess(word), listed);
+        }
+    }
+
+    #[test]
+    fn test_empty_dictionary_is_an_error() {
+        assert!(matches!(
+            RustleGame::with_length(40),
+            Err(GameError::EmptyDictionary { word_length: 40 })
+        ));
+        assert!(RustleGame::from_word_lists(Vec::new(), Vec::new(), 5).is_err());
+    }
+
+    #[test]
+    fn test_guess_records_row() {
+        let mut game = RustleGame::new().unwrap();
+        game.word = "ABC".to_string();
+        game.guess("ACD");
+        game.guess("ABC");
+        assert_eq!(game.guesses().len(), 2);
+        assert_eq!(game.guesses()[1].word, "ABC");
+        assert_eq!(
+            game.guesses()[1].colorize(Theme::CLASSIC)[2],
+            "C".color(CORRECT_COLOR)
+        );
+    }
+
+    #[test]
+    fn test_keyboard_state_is_monotonic() {
+        let mut game = RustleGame::new().unwrap();
+        game.word = "CRANE".to_string();
+        game.guess("CLEAN");
+        assert_eq!(game.keyboard_state()[&'C'], LetterStatus::Correct);
+        assert_eq!(game.keyboard_state()[&'E'], LetterStatus::Misplaced);
+        assert_eq!(game.keyboard_state()[&'L'], LetterStatus::Absent);
+        game.guess("SCONE");
+        assert_eq!(game.keyboard_state()[&'C'], LetterStatus::Correct);
+        assert_eq!(game.keyboard_state()[&'E'], LetterStatus::Correct);
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut game = RustleGame::new().unwrap();
+        game.word = "CRANE".to_string();
+        assert!(!game.undo());
+
+        game.guess("CLEAN");
+        game.guess("SCONE");
+        assert!(game.undo());
+        assert_eq!(game.guesses().len(), 1);
+        assert_eq!(game.guessed_letters, HashSet::from(['L']));
+        assert_eq!(game.keyboard_state()[&'E'], LetterStatus::Misplaced);
+        assert!(!game.keyboard_state().contains_key(&'S'));
+    }
+
+    #[test]
+    fn test_mulligan() {
+        let mut game = RustleGame::new().unwrap();
+        game.word = "CRANE".to_string();
+        game.guess("SLATE");
+        assert!(game.mulligan().is_err());
+
+        let mut game = game.with_daily(true).with_mulligan(true);
+        let mut output = Vec::new();
+        let action = game
+            .ask_for_guess_from("/undo\n/mulligan\n/mulligan\n".as_bytes(), &mut output)
+            .unwrap();
+        assert_eq!(action, GuessAction::Quit);
+        assert!(game.guesses().is_empty());
+        assert_eq!(game.remaining_guesses(), MAX_TRIES);
+        assert!(game.mulligan_used());
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("can't be undone"));
+        assert!(output.contains("already used today's mulligan"));
+
+        game.guess("SLATE");
+        assert!(game.mulligan().is_err());
+        assert!(game.replay().mulligan().is_err());
+
+        let mut spent = RustleGame::new().unwrap().with_daily(true);
+        spent.guess("SLATE");
+        assert!(spent.mulligan().is_err());
+    }
+
+    #[test]
+    fn test_infer_length() {
+        let words: Vec<String> = ["CAT", "CRANE", "DOG", "SLATE", "PLANET", "GRACE"]
+            .map(String::from)
+            .into();
+        assert_eq!(infer_length(&words), 5);
+        assert_eq!(infer_length(&words[..4]), 3);
+        assert_eq!(infer_length(&["ÉCOLE".to_string()]), 5);
+        assert_eq!(infer_length(&[]), WORD_LENGTH);
+    }
+
+    #[test]
+    fn test_length_histogram() {
+        let words = dictionary_words(
+            "cat\ncrane 120\nDog\n\nslate\ncrane\nplanet\n",
+            Charset::Ascii,
+        );
+        assert_eq!(words, ["CAT", "CRANE", "DOG", "SLATE", "PLANET"]);
+        assert_eq!(
+            length_histogram(&words),
+            BTreeMap::from([(3, 2), (5, 2), (6, 1)])
+        );
+        assert!(length_histogram(&[]).is_empty());
+        assert_eq!(length_histogram(&builtin_words())[&5], words_list(5).len());
+    }
+
+    #[test]
+    fn test_sanitize_phrase() {
+        assert_eq!(sanitize_phrase("  ice   cream!\r"), "ICE CREAM");
+        assert_eq!(sanitize_phrase("crane"), "CRANE");
+        assert_eq!(sanitize_phrase("- hot dog"), "HOT DOG");
+    }
+
+    #[test]
+    fn test_phrase_game() {
+        let phrases = vec![
+            "ICE CREAM".to_string(),
+            "HOT DOGS".to_string(),
+            "ICE CREPE".to_string(),
+            "ICED TEAS".to_string(),
+        ];
+        let mut game = RustleGame::from_phrases(phrases).unwrap();
+        game.word = "ICE CREAM".to_string();
+        game.word_length = 9;
+
+        assert_eq!(
+            game.submit("iced teas"),
+            Err(InvalidGuess::WrongShape {
+                shape: "___ _____".to_string()
+            })
+        );
+        assert_eq!(
+            game.submit("hot dogs"),
+            Err(InvalidGuess::WrongShape {
+                shape: "___ _____".to_string()
+            })
+        );
+        assert_eq!(game.submit("ice crepe"), Ok(GameStatus::InProgress));
+        assert_eq!(game.guesses()[0].statuses[3], LetterStatus::Correct);
+        assert!(!game.keyboard_state().contains_key(&' '));
+        assert_eq!(game.candidates(), vec!["ICE CREAM"]);
+        assert_eq!(
+            render_guess(&game.guesses()[0], RenderMode::Symbols, Theme::default()),
+            "[I][C][E]   [C][R][E] P  E "
+        );
+    }
+
+    #[test]
+    fn test_letter_frequencies() {
+        let mut game = RustleGame::replay_of(
+            "CRANE".to_string(),
+            ["CRANE", "CRATE", "GRACE", "TRACE", "BRAVE", "SLATE"]
+                .map(String::from)
+                .to_vec(),
+        );
+        game.guess("SLATE");
+        let frequencies = game.letter_frequencies();
+        assert_eq!(frequencies[0], ('R', 3));
+        assert_eq!(frequencies[1], ('C', 2));
+        assert!(frequencies.iter().all(|(c, _)| !"SLATE".contains(*c)));
+        assert!(frequencies.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    }
+
+    #[test]
+    fn test_position_odds() {
+        let mut game = RustleGame::replay_of(
+            "CRANE".to_string(),
+            ["CRANE", "CRATE", "GRACE", "TRACE", "BRAVE", "SLATE"]
+                .map(String::from)
+                .to_vec(),
+        );
+        assert_eq!(game.position_odds(), None);
+
+        game.guess("SLATE");
+        let odds = game.position_odds().unwrap();
+        assert_eq!(odds.len(), 5);
+        assert_eq!(odds[1], vec![('R', 1.0)]);
+        let first: Vec<char> = odds[0].iter().map(|(c, _)| *c).collect();
+        assert_eq!(first, vec!['B', 'C', 'G']);
+        assert!((odds[0][0].1 - 1.0 / 3.0).abs() < 1e-9);
+        assert!(odds
+            .iter()
+            .all(|letters| (letters.iter().map(|(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_invalid_letters_are_sorted() {
+        let mut game = RustleGame::new().unwrap();
+        let mut out = Vec::new();
+        game.write_invalid_letters(&mut out).unwrap();
+        assert!(out.is_empty());
+
+        game.guessed_letters = HashSet::from(['C', 'A', 'B']);
+        game.write_invalid_letters(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Letters not in the word: A B C\n"
+        );
+    }
+
+    #[test]
+    fn test_nearest() {
+        let dictionary: Vec<String> = ["FRONT", "FROST", "CRANE", "BLOOD"]
+            .map(String::from)
+            .into();
+        let mut game = RustleGame::replay_of("CRANE".to_string(), dictionary);
+        assert_eq!(game.nearest("FROOT").as_deref(), Some("FRONT"));
+        assert_eq!(game.nearest("CRANX").as_deref(), Some("CRANE"));
+        assert_eq!(game.nearest("ZZZZZ"), None);
+        assert_eq!(game.nearest("FRON"), None);
+
+        let mut output = Vec::new();
+        game.ask_for_guess_from("froot\n".as_bytes(), &mut output)
+            .unwrap();
+        assert!(String::from_utf8(output)
+            .unwrap()
+            .contains("FROOT isn't in the Rustle dictionary. Did you mean FRONT?"));
+    }
+
+    #[test]
+    fn test_hints_can_be_turned_off() {
+        let mut game = RustleGame::new().unwrap().with_hints_enabled(false);
+        game.word = "CRANE".to_string();
+        game.guess("SLOTH");
+        let mut out = Vec::new();
+        game.write_guesses(&mut out).unwrap();
+        game.write_prompt(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(!out.contains("Letters not in the word"));
+        assert_eq!(out.lines().count(), MAX_TRIES + 2);
+    }
+
+    #[test]
+    fn test_reused_absent_letters() {
+        let mut game = RustleGame::new().unwrap();
+        game.word = "CRANE".to_string();
+        game.guess("EERIE");
+        game.guess("SLOTH");
+        assert_eq!(
+            game.reused_absent_letters("TOILS"),
+            vec!['T', 'O', 'I', 'L', 'S']
+        );
+        assert_eq!(game.reused_absent_letters("EERIE"), vec!['I']);
+        assert!(game.reused_absent_letters("CRANE").is_empty());
+    }
+
+    #[test]
+    fn test_assist_counts_the_remaining_words() {
+        let dictionary: Vec<String> = ["CRANE", "GRACE", "BRAVE", "SLOTH"]
+            .map(String::from)
+            .into();
+        let mut game = RustleGame::replay_of("CRANE".to_string(), dictionary).with_assist(true);
+        let board = |game: &RustleGame| {
+            let mut out = Vec::new();
+            game.write_guesses(&mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert!(!board(&game).contains("remain"));
+        game.guess("SLOTH");
+        assert!(board(&game).contains("3 possible words remain."));
+        game.guess("GRACE");
+        assert!(board(&game).contains("Only one word fits!"));
+        game.guess("CRANE");
+        assert!(!board(&game).contains("fits"));
+    }
+
+    #[test]
+    fn test_coach_never_guesses_for_the_player() {
+        let dictionary: Vec<String> = ["CRANE", "GRACE", "BRAVE", "SLOTH"]
+            .map(String::from)
+            .into();
+        let mut game = RustleGame::replay_of("CRANE".to_string(), dictionary).with_coach(true);
+        game.guess("SLOTH");
+        let mut output = Vec::new();
+        let action = game
+            .ask_for_guess_from("/hint\n".as_bytes(), &mut output)
+            .unwrap();
+        assert_eq!(action, GuessAction::Quit);
+        assert_eq!(game.guesses().len(), 1);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("The coach would try"));
+
+        let mut output = Vec::new();
+        game.write_guesses(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("3 possible words remain."));
+        assert!(output.contains("/hint for the most informative next guess"));
+    }
+
+    #[test]
+    fn test_coach_rates_each_guess() {
+        let dictionary: Vec<String> = ["CRANE", "GRACE", "BRAVE", "SLOTH"]
+            .map(String::from)
+            .into();
+        let mut game = RustleGame::replay_of("CRANE".to_string(), dictionary).with_coach(true);
+        assert_eq!(game.rate_last_guess(), None);
+        game.guess("SLOTH");
+        let (rating, after, best_after) = game.rate_last_guess().unwrap();
+        assert_eq!(after, 3);
+        assert!(best_after < after);
+        assert_eq!(rating, solver::Rating::Poor);
+
+        let mut output = Vec::new();
+        game.write_guesses(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Poor guess: it left 3 words"));
+    }
+
+    #[test]
+    fn test_reset_with_new_word() {
+        let dictionary: Vec<String> = ["CRANE", "GRACE", "BRAVE"].map(String::from).into();
+        let mut game = RustleGame::replay_of("CRANE".to_string(), dictionary.clone())
+            .with_max_tries(3)
+            .with_hard_mode(true);
+        game.guess("GRACE");
+        let mut rng = RandomNumberGenerator::seeded(7);
+        for _ in 0..10 {
+            game.reset_with_new_word(&mut rng);
+            assert!(dictionary.contains(&game.word));
+            assert!(game.guesses().is_empty());
+            assert!(game.guessed_letters.is_empty() && game.keyboard_state.is_empty());
+            assert_eq!(game.max_tries(), 3);
+            assert!(game.hard_mode);
+            assert!(game.autoplay().won);
+        }
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dictionary: Vec<String> = ["CRANE", "SLATE", "TRACE"].map(String::from).into();
+        let builder = RustleGame::builder()
+            .answers(dictionary.clone())
+            .dictionary(dictionary);
+        let mut game = builder
+            .clone()
+            .word("CRANE")
+            .max_tries(4)
+            .hard_mode(true)
+            .build()
+            .unwrap();
+        game.guess("SLATE");
+        game.guess("TRACE");
+
+        let dir = std::env::temp_dir().join(format!("rustle-save-{}", std::process::id()));
+        let path = dir.join("game.json");
+        assert!(RustleGame::load(&path, &builder).unwrap().is_none());
+        game.save(&path).unwrap();
+        let loaded = RustleGame::load(&path, &builder).unwrap().unwrap();
+        fs::remove_dir_all(dir).unwrap();
+
+        assert_eq!(loaded.word(), "CRANE");
+        assert_eq!(loaded.guesses(), game.guesses());
+        assert_eq!(loaded.guessed_letters, game.guessed_letters);
+        assert_eq!(loaded.keyboard_state, game.keyboard_state);
+        assert_eq!(loaded.max_tries(), 4);
+        assert!(loaded.hard_mode());
+        assert_eq!(loaded.render_board(), game.render_board());
+    }
+
+    #[test]
+    fn test_legend_only_before_the_first_guess() {
+        let dictionary: Vec<String> = ["CRANE", "SLATE"].map(String::from).into();
+        let prompt = |game: &mut RustleGame| {
+            let mut output = Vec::new();
+            game.ask_for_guess_from("/quit\n".as_bytes(), &mut output)
+                .unwrap();
+            String::from_utf8(output).unwrap()
+        };
+        let mut game = RustleGame::replay_of("CRANE".to_string(), dictionary)
+            .with_render_mode(RenderMode::Symbols);
+        assert!(prompt(&mut game).contains("[A] right spot"));
+        game.guess("SLATE");
+        assert!(!prompt(&mut game).contains("right spot"));
+
+        let mut game = game.replay().with_legend(false);
+        assert!(!prompt(&mut game).contains("right spot"));
+    }
+
+    #[test]
+    fn test_pattern_code() {
+        assert_eq!(pattern_code(&evaluate("CRANE", "CANES")), 201);
+        assert_eq!(pattern_code(&evaluate("CRANE", "CRANE")), 242);
+        assert_eq!(pattern_code(&evaluate("CRANE", "PILOT")), 0);
+        assert_eq!(pattern_code(&[]), 0);
+        assert_eq!(decode_pattern(201, 5), evaluate("CRANE", "CANES"));
+        for code in 0..3u32.pow(5) {
+            assert_eq!(pattern_code(&decode_pattern(code, 5)), code);
+        }
+        let longest = vec![LetterStatus::Correct; MAX_PATTERN_LENGTH];
+        assert_eq!(
+            decode_pattern(pattern_code(&longest), MAX_PATTERN_LENGTH),
+            longest
+        );
+    }
+
+    #[test]
+    fn test_hide_answer() {
+        let dictionary: Vec<String> = ["CRANE", "SLATE"].map(String::from).into();
+        let mut game = RustleGame::replay_of("CRANE".to_string(), dictionary).with_max_tries(1);
+        game.guess("SLATE");
+        assert_eq!(
+            game.loss_message(),
+            "You ran out of tries! The word was CRANE"
+        );
+        let hidden = game.with_hide_answer(true);
+        assert!(!hidden.loss_message().contains("CRANE"));
+        assert_eq!(hidden.revealed_word(), None);
+        assert!(!hidden.replay().loss_message().contains("CRANE"));
+        assert_eq!(hidden.word(), "CRANE");
+    }
+
+    #[test]
+    fn test_weighted_pick() {
+        let weights = [1.0, 0.0, 3.0, 6.0];
+        let mut rng = RandomNumberGenerator::seeded(86);
+        let mut counts = [0_usize; 4];
+        for _ in 0..10_000 {
+            counts[weighted_pick(&weights, &mut rng).unwrap()] += 1;
+        }
+        assert_eq!(counts[1], 0);
+        for (&count, expected) in counts.iter().zip([1_000, 0, 3_000, 6_000]) {
+            assert!(count.abs_diff(expected) < 300, "{counts:?}");
+        }
+        assert_eq!(weighted_pick(&[0.0, 0.0], &mut rng), None);
+        assert_eq!(weighted_pick(&[], &mut rng), None);
+    }
+
+    #[test]
+    fn test_no_repeats_until_the_pool_cycles() {
+        let dictionary: Vec<String> = ["CRANE", "GRACE", "BRAVE", "SLATE", "TRACE"]
+            .map(String::from)
+            .into();
+        let mut game = RustleGame::replay_of("CRANE".to_string(), dictionary.clone());
+        let mut rng = RandomNumberGenerator::seeded(3);
+        let mut played = vec![game.word().to_string()];
+        for _ in 0..9 {
+            game.reset_with_new_word(&mut rng);
+            played.push(game.word().to_string());
+        }
+        for cycle in played.chunks(dictionary.len()) {
+            let distinct: HashSet<&String> = cycle.iter().collect();
+            assert_eq!(distinct.len(), dictionary.len());
+        }
+        assert_eq!(game.used_words().len(), dictionary.len());
+    }
+
+    #[test]
+    fn test_with_word() {
+        let dictionary = vec!["CRANE".to_string(), "SLATE".to_string()];
+        let game = RustleGame::with_word(" slate\n", dictionary.clone()).unwrap();
+        assert_eq!(game.word(), "SLATE");
+        assert_eq!(game.word_length(), 5);
+        assert!(matches!(
+            RustleGame::with_word("CRANX", dictionary.clone()),
+            Err(GameError::UnknownWord(word)) if word == "CRANX"
+        ));
+        assert!(RustleGame::with_word("CRAN", dictionary).is_err());
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut game = RustleGame::new().unwrap().with_max_tries(4);
+        game.word = "CRANE".to_string();
+        assert!(game.peek().is_err());
+
+        game = game.with_peeks(true);
+        game.guess("CRONY");
+        assert_eq!(game.peek(), Ok(2));
+        assert_eq!(game.guesses()[1].word, "__A__");
+        assert_eq!(game.guesses()[1].statuses[2], LetterStatus::Correct);
+        assert_eq!(game.remaining_guesses(), 2);
+        assert_eq!(
+            game.keyboard_state().get(&'A'),
+            Some(&LetterStatus::Correct)
+        );
+        assert!(!game.keyboard_state().contains_key(&PEEK_PLACEHOLDER));
+        let candidates = game.candidates();
+        assert!(candidates.contains(&"CRANE".to_string()));
+        assert!(candidates.iter().all(|word| word.starts_with("CRAN")));
+        assert_eq!(game.check_status(), GameStatus::InProgress);
+
+        // The only unknown letter left can't be peeked at.
+        assert!(game.peek().is_err());
+        game.guess("CRANE");
+        assert_eq!(game.check_status(), GameStatus::Won { tries: 3 });
+    }
+
+    #[test]
+    fn test_peek_limits() {
+        let mut game = RustleGame::new().unwrap().with_peeks(true);
+        game.word = "CRANE".to_string();
+        assert_eq!(game.peek(), Ok(0));
+        assert_eq!(game.peek(), Ok(1));
+        assert_eq!(game.peeks_used(), MAX_PEEKS);
+        assert!(game.peek().is_err());
+
+        game.undo();
+        game = game.with_max_tries(2);
+        assert!(game.peek().is_err());
+    }
+
+    #[test]
+    fn test_replay_of() {
+        let mut game = RustleGame::replay_of(
+            "CRANE".to_string(),
+            vec!["CRANE".to_string(), "SLATE".to_string()],
+        );
+        assert_eq!(game.word(), "CRANE");
+        assert_eq!(game.word_length(), 5);
+        assert!(game.is_valid_guess("SLATE"));
+
+        game.guess("SLATE");
+        let replay = game.with_max_tries(3).with_hard_mode(true).replay();
+        assert_eq!(replay.word(), "CRANE");
+        assert_eq!(replay.max_tries(), 3);
+        assert!(replay.guesses().is_empty());
+        assert!(replay.keyboard_state().is_empty());
+        assert!(replay.guessed_letters.is_empty());
+    }
+
+    #[test]
+    fn test_explore_leaves_the_game_alone() {
+        let mut game = RustleGame::new().unwrap().with_hard_mode(true);
+        game.word = "CRANE".to_string();
+        game.guess("CRANE");
+        let explored = game.explore(" slate ").unwrap();
+        assert_eq!(explored.word, "SLATE");
+        assert_eq!(explored.statuses, evaluate("CRANE", "SLATE"));
+        assert_eq!(game.guesses().len(), 1);
+        assert_eq!(game.check_status(), GameStatus::Won { tries: 1 });
+        assert_eq!(
+            game.explore("ZZZZZ").unwrap_err(),
+            InvalidGuess::NotInDictionary("ZZZZZ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_distinct_first() {
+        let mut game = RustleGame::new().unwrap().with_distinct_first(true);
+        game.word = "CRANE".to_string();
+        assert_eq!(game.validate("EERIE"), Err(InvalidGuess::RepeatedLetters));
+        assert_eq!(game.validate("SLATE"), Ok(()));
+        game.guess("SLATE");
+        assert_eq!(game.validate("EERIE"), Ok(()));
+        assert_eq!(
+            game.replay().validate("EERIE"),
+            Err(InvalidGuess::RepeatedLetters)
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut game = RustleGame::new().unwrap().with_hard_mode(true);
+        game.word = "CRANE".to_string();
+        assert_eq!(
+            game.validate("CRAN"),
+            Err(InvalidGuess::WrongLength { expected: 5 })
+        );
+        assert_eq!(
+            game.validate("ZZZZZ"),
+            Err(InvalidGuess::NotInDictionary("ZZZZZ".to_string()))
+        );
+        assert_eq!(game.validate("SLATE"), Ok(()));
+        assert!(game.guesses().is_empty());
+
+        game.guess("SLATE");
+        assert_eq!(
+            game.validate("PRINT"),
+            Err(InvalidGuess::HardMode(
+                "Must use A in position 3".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_allow_any() {
+        let dictionary = vec!["CRANE".to_string(), "SLATE".to_string()];
+        let mut strict = RustleGame::with_word("crane", dictionary.clone()).unwrap();
+        assert_eq!(
+            strict.submit("crxnq"),
+            Err(InvalidGuess::NotInDictionary("CRXNQ".to_string()))
+        );
+
+        let mut game = RustleGame::with_word("crane", dictionary)
+            .unwrap()
+            .with_allow_any(true);
+        assert_eq!(game.submit("crxnq"), Ok(GameStatus::InProgress));
+        assert_eq!(
+            game.guesses()[0].statuses,
+            vec![
+                LetterStatus::Correct,
+                LetterStatus::Correct,
+                LetterStatus::Absent,
+                LetterStatus::Correct,
+                LetterStatus::Absent
+            ]
+        );
+        assert_eq!(
+            game.submit("crxn"),
+            Err(InvalidGuess::WrongLength { expected: 5 })
+        );
+        assert!(game.replay().allow_any);
+    }
+
+    #[test]
+    fn test_non_letters_are_rejected() {
+        let mut game = RustleGame::with_word("hello", vec!["HELLO".to_string()]).unwrap();
+        assert_eq!(game.submit("he11o"), Err(InvalidGuess::NotLetters));
+        assert_eq!(game.submit("12345"), Err(InvalidGuess::NotLetters));
+        assert_eq!(
+            game.submit("hell"),
+            Err(InvalidGuess::WrongLength { expected: 5 })
+        );
+        assert!(game.guesses().is_empty());
+        assert_eq!(game.submit("  hello\r\n"), Ok(GameStatus::Won { tries: 1 }));
+
+        let mut output = Vec::new();
+        let mut game = game.replay();
+        game.ask_for_guess_from("he11o\n".as_bytes(), &mut output)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Guesses must be letters only."));
+        assert!(!output.contains("must be 5 letters"));
+    }
+
+    #[test]
+    fn test_hard_mode_violation() {
+        let mut game = RustleGame::new().unwrap().with_hard_mode(true);
+        game.word = "CRANE".to_string();
+        game.guess("CLEAN");
+        assert_eq!(
+            game.hard_mode_violation("STORY"),
+            Some("Must use C in position 1".to_string())
+        );
+        assert_eq!(
+            game.hard_mode_violation("CHIRP"),
+            Some("Guess must contain A".to_string())
+        );
+        assert_eq!(game.hard_mode_violation("CANES"), None);
+
+        let mut easy = RustleGame::new().unwrap();
+        easy.word = "CRANE".to_string();
+        easy.guess("CLEAN");
+        assert_eq!(easy.hard_mode_violation("STORY"), None);
+    }
+}
@@ -1,288 +1,1498 @@
 #![warn(clippy::all, clippy::pedantic)]
 
 use bracket_random::prelude::RandomNumberGenerator;
-use colored::{Color, ColoredString, Colorize};
+use colored::Colorize;
+use rustle::{
+    boards::Boards, format_duration, help, json_error, json_guess, json_loss, json_result,
+    leaderboard, openers::Openers, render_guess, saved::SavedGame, score_game, solver,
+    tournament::Tournament, transcript::Transcript, Charset, Config, DictionaryEntry, Difficulty,
+    GameError, GameStatus, GuessAction, Leaderboard, Mode, Numbering, RenderMode, RustleGame,
+    RustleGameBuilder, Stats, Theme, MAX_TRIES, QUIT_COMMAND, REPLAY_COMMAND, REVEAL_COMMAND,
+    WORD_LENGTH,
+};
 use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+use std::path::Path;
 
-const ALL_WORDS: &str = include_str!("words.txt");
+/// Passing this as a word list path reads the list from stdin.
+const STDIN_PATH: &str = "-";
+/// Picks the word, like `--answer`, for scripted demos. `--answer` wins if
+/// both are given, and the daily game ignores it.
+const ANSWER_VAR: &str = "RUSTLE_ANSWER";
 
-const WORD_LENGTH: usize = 5;
-const MAX_TRIES: usize = 6;
+const USAGE: &str = "\
+Usage: rustle --stats
+       rustle --status-line
+       rustle --list-themes | --list-modes
+       rustle --dict-info [--dict PATH] [--length N] [--unicode]
+       rustle --match PATTERN [--dict PATH] [--answers PATH] [--unicode]
+       rustle --best-opener [--sample K] [--dict PATH] [--answers PATH] [--length N] [--unicode]
+       rustle [--length N] [--tries N] [--zen] [--mode letters|distance] [--hard] [--no-hard] [--distinct-first] [--daily] [--seed N]
+              [--color] [--symbols] [--describe] [--theme NAME] [--classic-gray] [--numbering one|zero|total] [--dict PATH] [--answers PATH] [--phrases PATH] [--blocklist PATH] [--block-guesses] [--unicode] [--freqs] [--assist] [--coach] [--hints] [--no-hints] [--allow-any]
+              [--difficulty easy|medium|hard] [--weighted] [--top-n K] [--strict] [--timed] [--no-share] [--hide-answer] [--no-legend] [--resume] [--practice] [--allow-repeats] [--auto]
+              [--json] [--name NAME] [--explain] [--explore] [--tournament N] [--dordle]
+              [--log PATH]";
 
-const CORRECT_COLOR: Color = Color::BrightGreen;
-const MISPLACED_COLOR: Color = Color::BrightYellow;
-const INCORRECT_COLOR: Color = Color::BrightRed;
+/// How a game ended, which is also the exit code, so scripts can tell
+/// without reading the output. In a practice session or a tournament the
+/// last game counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Won = 0,
+    Lost = 1,
+    Quit = 2,
+}
 
-fn main() {
-    let mut game = RustleGame::new();
-    loop {
-        game.display_guesses();
-        let guess = game.ask_for_guess();
-        if game.is_game_over(&guess) {
-            break;
+impl Outcome {
+    /// The outcome of `game`, or [`Outcome::Quit`] if it wasn't `finished`.
+    /// A game given up with `/reveal` is lost.
+    fn of(game: &RustleGame, finished: bool) -> Outcome {
+        if !finished {
+            Outcome::Quit
+        } else if matches!(game.check_status(), GameStatus::Won { .. }) {
+            Outcome::Won
+        } else {
+            Outcome::Lost
         }
     }
+
+    fn exit(self) -> ! {
+        std::process::exit(self as i32)
+    }
 }
 
-fn words_list() -> Vec<String> {
-    ALL_WORDS
-        .split('\n')
-        .skip(2)
-        .map(sanitize_word)
-        .filter(|line| line.len() == WORD_LENGTH)
-        .collect()
+/// The exit code when the game can't be played at all, e.g. for a bad flag
+/// or a word list that can't be read.
+const EXIT_ERROR: i32 = 3;
+
+/// How many words are guessed at once with `--dordle`.
+const DORDLE_BOARDS: usize = 2;
+
+struct WordLists {
+    answers: Vec<String>,
+    valid_guesses: Vec<String>,
+    /// How common each answer is, for `--weighted`; empty otherwise.
+    weights: Vec<f64>,
 }
 
-fn sanitize_word(word: &str) -> String {
-    word.trim()
-        .to_uppercase()
-        .chars()
-        .filter(char::is_ascii_alphabetic)
-        .collect()
+#[allow(clippy::struct_excessive_bools)]
+struct Options {
+    length: usize,
+    /// Whether the length was picked with `--length`, rather than left for
+    /// the config or the dictionary to decide.
+    length_given: bool,
+    tries: usize,
+    /// Whether the tries were picked with `--tries`, rather than left for
+    /// the config.
+    tries_given: bool,
+    zen: bool,
+    hard_mode: bool,
+    /// Whether hard mode was turned on or off with `--hard` or `--no-hard`,
+    /// rather than left for the config.
+    hard_mode_given: bool,
+    daily: bool,
+    seed: Option<u64>,
+    render_mode: RenderMode,
+    numbering: Numbering,
+    mode: Mode,
+    theme: Theme,
+    dictionary: Option<String>,
+    answers: Option<String>,
+    phrases: Option<String>,
+    /// Words that are never picked as the answer.
+    blocklist: Option<String>,
+    /// Whether the blocked words are rejected as guesses too.
+    block_guesses: bool,
+    /// The hidden word, when it's picked by hand. Left out of the usage on
+    /// purpose, since it spoils the game.
+    answer: Option<String>,
+    /// The word from `RUSTLE_ANSWER`, once it has been checked, for the
+    /// first game only.
+    env_answer: Option<String>,
+    charset: Charset,
+    difficulty: Difficulty,
+    /// Whether common answers are picked more often.
+    weighted: bool,
+    /// Keeps only this many of the most common words, as answers and as
+    /// guesses.
+    top_n: Option<usize>,
+    strict: bool,
+    timed: bool,
+    freqs: bool,
+    assist: bool,
+    coach: bool,
+    distinct_first: bool,
+    hints: bool,
+    /// Whether to show the keyboard and the letters not in the word.
+    hints_enabled: bool,
+    /// Whether to keep the word to yourself when the game is lost.
+    hide_answer: bool,
+    /// Whether the first prompt explains what the colors mean.
+    legend: bool,
+    allow_any: bool,
+    share: bool,
+    practice: bool,
+    /// Whether practice games may pick a word already played this session.
+    repeats: bool,
+    auto: bool,
+    json: bool,
+    show_stats: bool,
+    /// Prints a one-line summary of the game in progress, instead of
+    /// playing.
+    status_line: bool,
+    /// Lists the color themes, instead of playing.
+    list_themes: bool,
+    /// Lists the render modes, game modes, difficulties and numberings,
+    /// instead of playing.
+    list_modes: bool,
+    /// Whether to pick up the game left off last time, if there is one.
+    resume: bool,
+    /// Lists the words fitting this pattern, like `CR.N.`, instead of
+    /// playing.
+    pattern: Option<String>,
+    /// Whether to rate opening guesses against the word before playing.
+    explain: bool,
+    /// Shows the best opening guesses for the answers, instead of playing.
+    best_opener: bool,
+    /// Rates the openers over only this many answers, to be quicker.
+    sample: Option<usize>,
+    /// Shows how many words of each length the dictionary has, instead of
+    /// playing.
+    dict_info: bool,
+    /// Whether to offer to keep guessing after a win, to see how other
+    /// words would have scored.
+    explore: bool,
+    /// Plays this many games in a row, then shows how they went.
+    tournament: Option<usize>,
+    /// Whether to guess two words at once, on boards side by side.
+    dordle: bool,
+    /// Appends a transcript of every finished game to this file.
+    log: Option<String>,
+    /// Who the points of won games go to on the leaderboard.
+    name: String,
 }
 
-struct RustleGame {
-    dictionary: Vec<String>,
-    word: String,
-    guessed_letters: HashSet<char>,
-    guesses: Vec<Vec<ColoredString>>,
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            length: WORD_LENGTH,
+            length_given: false,
+            tries: MAX_TRIES,
+            tries_given: false,
+            zen: false,
+            hard_mode: false,
+            hard_mode_given: false,
+            daily: false,
+            seed: None,
+            render_mode: RenderMode::Color,
+            numbering: Numbering::default(),
+            mode: Mode::default(),
+            theme: Theme::default(),
+            dictionary: None,
+            answers: None,
+            phrases: None,
+            blocklist: None,
+            block_guesses: false,
+            answer: None,
+            env_answer: None,
+            charset: Charset::Ascii,
+            difficulty: Difficulty::Hard,
+            weighted: false,
+            top_n: None,
+            strict: false,
+            timed: false,
+            freqs: false,
+            assist: false,
+            coach: false,
+            distinct_first: false,
+            hints: false,
+            hints_enabled: true,
+            hide_answer: false,
+            legend: true,
+            allow_any: false,
+            share: true,
+            practice: false,
+            repeats: false,
+            auto: false,
+            json: false,
+            show_stats: false,
+            status_line: false,
+            list_themes: false,
+            list_modes: false,
+            resume: false,
+            pattern: None,
+            explain: false,
+            best_opener: false,
+            sample: None,
+            dict_info: false,
+            explore: false,
+            tournament: None,
+            dordle: false,
+            log: None,
+            name: default_name(),
+        }
+    }
 }
 
-impl RustleGame {
-    fn new() -> Self {
-        let mut rng = RandomNumberGenerator::new();
-        let dictionary = words_list();
-        let word = rng.random_slice_entry(&dictionary).unwrap().clone();
-        Self {
-            dictionary,
-            word,
-            guessed_letters: HashSet::new(),
-            guesses: Vec::new(),
+impl Options {
+    /// Parses the flags in `args`, on top of the defaults from `config`.
+    /// Only the flags are checked for conflicts; the config fills in what
+    /// they left open afterwards.
+    fn parse(mut args: impl Iterator<Item = String>, config: &Config) -> Self {
+        let mut options = Options::default();
+        // Flags for these simply replace them, and they clash with nothing.
+        options.theme = config.theme.unwrap_or(options.theme);
+        options.render_mode = config.render_mode.unwrap_or(options.render_mode);
+        while let Some(arg) = args.next() {
+            options.apply(&arg, &mut args);
         }
+        options.check_conflicts();
+        options.apply_config(config);
+        options
     }
 
-    fn colorize_guess(&mut self, guess: &str) -> Vec<ColoredString> {
-        let mut guess_array: Vec<ColoredString> = guess
-            .chars()
-            .map(|c| c.to_string().color(INCORRECT_COLOR))
-            .collect();
+    /// Takes the length, tries and hard mode from `config` unless they were
+    /// given as flags. They are only defaults, so modes that fix them (the
+    /// daily game's length, zen mode's and Dordle's tries) or have no use
+    /// for them ignore them instead of clashing. A dictionary picks its own
+    /// length.
+    fn apply_config(&mut self, config: &Config) {
+        let fixed_length = self.daily
+            || self.dictionary.is_some()
+            || self.answers.is_some()
+            || self.phrases.is_some()
+            || self.pattern.is_some();
+        if let Some(length) = config
+            .length
+            .filter(|_| !self.length_given && !fixed_length)
+        {
+            self.length = length;
+        }
+        if let Some(tries) = config
+            .tries
+            .filter(|_| !self.tries_given && !self.zen && !self.dordle)
+        {
+            self.tries = tries;
+        }
+        if let Some(hard_mode) = config
+            .hard_mode
+            .filter(|_| !self.hard_mode_given && self.mode != Mode::Distance)
+        {
+            self.hard_mode = hard_mode;
+        }
+    }
 
-        let mut char_count: HashMap<char, i32> = create_charmap(&self.word);
+    /// Applies the flag `arg`, taking its value from `args` if it has one.
+    fn apply(&mut self, arg: &str, args: &mut impl Iterator<Item = String>) {
+        match arg {
+            "--length" => {
+                self.length = parse_number(arg, args.next().as_deref());
+                self.length_given = true;
+            }
+            "--tries" => {
+                self.tries = parse_number(arg, args.next().as_deref());
+                self.tries_given = true;
+            }
+            "--zen" => self.zen = true,
+            "--hard" | "--no-hard" => {
+                self.hard_mode = arg == "--hard";
+                self.hard_mode_given = true;
+            }
+            "--daily" => self.daily = true,
+            "--dict" => self.dictionary = Some(parse_path(arg, args.next())),
+            "--answers" => self.answers = Some(parse_path(arg, args.next())),
+            "--phrases" => self.phrases = Some(parse_path(arg, args.next())),
+            "--blocklist" => self.blocklist = Some(parse_path(arg, args.next())),
+            "--block-guesses" => self.block_guesses = true,
+            "--answer" => self.answer = Some(parse_word(args.next())),
+            "--unicode" => self.charset = Charset::Unicode,
+            "--difficulty" => self.difficulty = parse_difficulty(args.next().as_deref()),
+            "--strict" => self.strict = true,
+            "--weighted" => self.weighted = true,
+            "--top-n" => self.top_n = Some(parse_number(arg, args.next().as_deref())),
+            "--timed" => self.timed = true,
+            "--freqs" => self.freqs = true,
+            "--assist" => self.assist = true,
+            "--coach" => self.coach = true,
+            "--distinct-first" => self.distinct_first = true,
+            "--hints" => self.hints = true,
+            "--no-hints" => self.hints_enabled = false,
+            "--hide-answer" => self.hide_answer = true,
+            "--no-legend" => self.legend = false,
+            "--allow-any" => self.allow_any = true,
+            "--no-share" => self.share = false,
+            "--practice" => self.practice = true,
+            "--allow-repeats" => self.repeats = true,
+            "--auto" => self.auto = true,
+            "--json" => self.json = true,
+            "--stats" => self.show_stats = true,
+            "--status-line" => self.status_line = true,
+            "--list-themes" => self.list_themes = true,
+            "--list-modes" => self.list_modes = true,
+            "--resume" => self.resume = true,
+            "--explain" => self.explain = true,
+            "--best-opener" => self.best_opener = true,
+            "--sample" => self.sample = Some(parse_number(arg, args.next().as_deref())),
+            "--dict-info" => self.dict_info = true,
+            "--explore" => self.explore = true,
+            "--log" => self.log = Some(parse_path(arg, args.next())),
+            "--tournament" => self.tournament = Some(parse_number(arg, args.next().as_deref())),
+            "--dordle" => self.dordle = true,
+            "--match" => self.pattern = Some(parse_pattern(args.next())),
+            "--name" => self.name = parse_name(args.next()),
+            "--color" => self.render_mode = RenderMode::Color,
+            "--symbols" => self.render_mode = RenderMode::Symbols,
+            "--describe" => self.render_mode = RenderMode::Describe,
+            "--mode" => self.mode = parse_mode(args.next().as_deref()),
+            "--numbering" => self.numbering = parse_numbering(args.next().as_deref()),
+            "--theme" => self.theme = parse_theme(args.next().as_deref()),
+            "--classic-gray" => self.theme = Theme::CLASSIC_GRAY,
+            "--seed" => self.seed = Some(parse_seed(args.next().as_deref())),
+            other => exit_with_usage(&format!("Unknown argument: {other}")),
+        }
+    }
 
-        // Set the correct letters to green
-        guess.chars().enumerate().for_each(|(pos, c)| {
-            if self.word.chars().nth(pos).unwrap() == c {
-                guess_array[pos] = format!("{c}").color(CORRECT_COLOR);
-                char_count.entry(c).and_modify(|e| *e -= 1);
-            };
-        });
+    /// Exits with the usage if flags that don't go together were given.
+    fn check_conflicts(&self) {
+        let custom_words = self.dictionary.is_some() || self.answers.is_some();
+        if self.daily && (self.length != WORD_LENGTH || custom_words) {
+            exit_with_usage("--daily always uses the default dictionary and word length");
+        }
+        if self.phrases.is_some()
+            && (custom_words
+                || self.daily
+                || self.length != WORD_LENGTH
+                || self.charset != Charset::Ascii)
+        {
+            exit_with_usage(
+                "--phrases can't be combined with other word lists, --length, --daily or --unicode",
+            );
+        }
+        if self.blocklist.is_some() && (self.daily || self.phrases.is_some()) {
+            exit_with_usage("--blocklist can't be combined with --daily or --phrases");
+        }
+        if self.block_guesses && self.blocklist.is_none() {
+            exit_with_usage("--block-guesses needs a --blocklist");
+        }
+        if self.answer.is_some() && (self.daily || self.seed.is_some() || self.phrases.is_some()) {
+            exit_with_usage("--answer can't be combined with --daily, --seed or --phrases");
+        }
+        if self.dictionary.as_deref() == Some(STDIN_PATH)
+            && self.answers.as_deref() == Some(STDIN_PATH)
+        {
+            exit_with_usage("Only one word list can be read from stdin");
+        }
+        if self.difficulty != Difficulty::Hard && !custom_words {
+            exit_with_usage(
+                "--difficulty needs a word list with frequencies from --dict or --answers",
+            );
+        }
+        if self.weighted && (!custom_words || self.phrases.is_some()) {
+            exit_with_usage(
+                "--weighted needs a word list with frequencies from --dict or --answers",
+            );
+        }
+        if self.top_n.is_some() && !custom_words {
+            exit_with_usage("--top-n needs a word list with frequencies from --dict or --answers");
+        }
+        if self.strict && !custom_words {
+            exit_with_usage("--strict needs a word list from --dict or --answers");
+        }
+        if self.resume
+            && (self.daily
+                || self.answer.is_some()
+                || self.auto
+                || self.json
+                || self.tournament.is_some())
+        {
+            exit_with_usage(
+                "--resume can't be combined with --daily, --answer, --auto, --json or --tournament",
+            );
+        }
+        if self.sample.is_some() && !self.best_opener {
+            exit_with_usage("--sample only goes with --best-opener");
+        }
+        if self.json && (self.practice || self.auto || self.explain) {
+            exit_with_usage("--json can't be combined with --practice, --auto or --explain");
+        }
+        if self.explain && self.hide_answer {
+            exit_with_usage("--explain shows the word, so it can't be combined with --hide-answer");
+        }
+        if self.pattern.as_ref().is_some_and(|pattern| {
+            self.length != WORD_LENGTH && pattern.chars().count() != self.length
+        }) {
+            exit_with_usage(
+                "--match patterns have one character per letter, so --length isn't needed",
+            );
+        }
+        if self.zen && self.tries_given {
+            exit_with_usage(
+                "--zen has no limit on the guesses, so it can't be combined with --tries",
+            );
+        }
+        if self.mode == Mode::Distance
+            && (self.hard_mode
+                || self.json
+                || self.assist
+                || self.coach
+                || self.hints
+                || self.freqs)
+        {
+            exit_with_usage(
+                "--mode distance doesn't score letters, so it can't be combined with --hard, --json, --assist, --coach, --hints or --freqs",
+            );
+        }
+        if self.hints && !self.hints_enabled {
+            exit_with_usage("--hints can't be combined with --no-hints");
+        }
+        if self.daily && self.practice {
+            exit_with_usage("--practice can't be combined with --daily");
+        }
+        self.check_session_conflicts();
+    }
 
-        // Set the correct but misplaced letters to yellow
-        guess.chars().enumerate().for_each(|(pos, c)| {
-            if is_color(&guess_array[pos], CORRECT_COLOR) {
-                return;
-            };
+    /// Exits with the usage if `--tournament` or `--dordle` was given with
+    /// flags they don't support.
+    fn check_session_conflicts(&self) {
+        if self.tournament.is_some()
+            && (self.daily
+                || self.practice
+                || self.json
+                || self.zen
+                || self.answer.is_some()
+                || self.pattern.is_some())
+        {
+            exit_with_usage(
+                "--tournament can't be combined with --daily, --practice, --json, --zen or --match",
+            );
+        }
+        if self.dordle
+            && (self.daily
+                || self.practice
+                || self.auto
+                || self.json
+                || self.zen
+                || self.resume
+                || self.tries_given
+                || self.tournament.is_some())
+        {
+            exit_with_usage(
+                "--dordle can't be combined with --daily, --practice, --auto, --json, --zen, --resume, --tries or --tournament",
+            );
+        }
+    }
 
-            self.word.chars().enumerate().for_each(|(i, ch)| {
-                if is_color(&guess_array[i], CORRECT_COLOR) {
-                    return;
-                };
+    /// Without a `--length`, plays with the most common word length of the
+    /// `--dict` file, or else the `--answers` file. A list read from stdin
+    /// can only be read once, so it keeps the default length.
+    fn infer_length(&mut self) {
+        if self.length_given || self.pattern.is_some() {
+            return;
+        }
+        let Some(path) = self
+            .dictionary
+            .as_deref()
+            .or(self.answers.as_deref())
+            .filter(|path| *path != STDIN_PATH)
+        else {
+            return;
+        };
+        // If it can't be read, loading it reports why.
+        let Ok(text) = std::fs::read_to_string(path) else {
+            return;
+        };
+        let words = rustle::dictionary_words(&text, self.charset);
+        if words.is_empty() {
+            eprintln!("{path} has no words, playing with {WORD_LENGTH}-letter words");
+        }
+        self.length = rustle::infer_length(&words);
+    }
 
-                if ch == c && char_count[&c] > 0 {
-                    guess_array[pos] = format!("{c}").color(MISPLACED_COLOR);
-                    char_count.entry(c).and_modify(|e| *e -= 1);
+    /// Loads the custom word lists, if any were given, or the embedded one
+    /// with a blocklist. This happens once at startup, so a list piped in on
+    /// stdin is only read once. The difficulty and the blocklist only narrow
+    /// down the answers: every word can still be guessed, unless the blocked
+    /// words are blocked as guesses too. `--top-n` trims both lists.
+    fn load_word_lists(&self) -> Result<Option<WordLists>, GameError> {
+        if let Some(path) = &self.phrases {
+            let phrases = rustle::load_phrase_file(path)?;
+            return Ok(Some(WordLists {
+                answers: phrases.clone(),
+                valid_guesses: phrases,
+                weights: Vec::new(),
+            }));
+        }
+        if self.dictionary.is_none() && self.answers.is_none() && self.blocklist.is_none() {
+            return Ok(None);
+        }
+        let mut dictionary = match &self.dictionary {
+            Some(path) => self.load_words(path)?,
+            None => rustle::words_list(self.length)
+                .into_iter()
+                .map(|word| DictionaryEntry {
+                    word,
+                    frequency: None,
+                })
+                .collect(),
+        };
+        let mut answer_entries = match &self.answers {
+            Some(path) => self.load_words(path)?,
+            None => dictionary.clone(),
+        };
+        if let Some(path) = &self.blocklist {
+            let blocklist = rustle::load_blocklist(path, self.charset)?;
+            rustle::remove_blocked(&mut answer_entries, &blocklist);
+            if self.block_guesses {
+                rustle::remove_blocked(&mut dictionary, &blocklist);
+            }
+        }
+        if let Some(count) = self.top_n {
+            if answer_entries
+                .iter()
+                .chain(&dictionary)
+                .all(|entry| entry.frequency.is_none())
+            {
+                eprintln!("The word list has no frequencies, so every word is kept");
+            }
+            rustle::keep_most_common(&mut dictionary, count);
+            rustle::keep_most_common(&mut answer_entries, count);
+        }
+        let answers = self.difficulty.answers(&answer_entries);
+        let weights = if self.weighted {
+            frequencies(&answers, &answer_entries)
+        } else {
+            Vec::new()
+        };
+        if self.weighted && weights.iter().all(|weight| *weight == 0.0) {
+            eprintln!("The word list has no frequencies, so every word is equally likely");
+        }
+        Ok(Some(WordLists {
+            answers,
+            valid_guesses: dictionary.into_iter().map(|entry| entry.word).collect(),
+            weights,
+        }))
+    }
+
+    fn load_words(&self, path: &str) -> Result<Vec<DictionaryEntry>, GameError> {
+        if path != STDIN_PATH {
+            return if self.strict {
+                rustle::load_dictionary_entries_strict(path, self.length, self.charset)
+            } else {
+                rustle::load_dictionary_entries(path, self.length, self.charset)
+            };
+        }
+        let words = if self.strict {
+            let text = std::io::read_to_string(std::io::stdin().lock())?;
+            rustle::check_dictionary_entries(&text, self.length, self.charset).map_err(|lines| {
+                GameError::RejectedLines {
+                    word_length: self.length,
+                    lines,
                 }
-            });
-        });
+            })?
+        } else {
+            rustle::read_dictionary_entries(std::io::stdin().lock(), self.length, self.charset)?
+        };
+        if !self.auto {
+            reattach_stdin_to_terminal();
+        }
+        Ok(words)
+    }
 
-        guess.chars().enumerate().for_each(|(pos, c)| {
-            if !is_color(&guess_array[pos], CORRECT_COLOR)
-                && !is_color(&guess_array[pos], MISPLACED_COLOR)
-            {
-                self.guessed_letters.insert(c);
+    /// Sets up the game for `round`, counting from 0. Every round of a seeded
+    /// session gets its own seed, so practice sessions are reproducible too.
+    fn new_game(
+        &self,
+        round: u64,
+        word_lists: Option<&WordLists>,
+    ) -> Result<RustleGame, GameError> {
+        self.game_builder(round, word_lists).build()
+    }
+
+    /// The builder for [`new_game`](Self::new_game), for adding more
+    /// settings.
+    fn game_builder(&self, round: u64, word_lists: Option<&WordLists>) -> RustleGameBuilder {
+        let mut builder = RustleGame::builder()
+            .length(self.length)
+            .max_tries(self.tries)
+            .zen(self.zen)
+            .hard_mode(self.hard_mode)
+            .render_mode(self.render_mode)
+            .numbering(self.numbering)
+            .mode(self.mode)
+            .theme(self.theme)
+            .charset(self.charset)
+            .timer(self.timed)
+            .frequency_hints(self.freqs)
+            .assist(self.assist)
+            .coach(self.coach)
+            .distinct_first(self.distinct_first)
+            .peeks(self.hints)
+            .position_hints(self.hints)
+            .hints_enabled(self.hints_enabled)
+            .hide_answer(self.hide_answer)
+            .legend(self.legend)
+            .allow_any(self.allow_any)
+            .daily(self.daily);
+        if let Some(seed) = self.seed {
+            builder = builder.seed(seed.wrapping_add(round));
+        }
+        if let Some(answer) = &self.answer {
+            builder = builder.word(answer);
+        } else if let Some(answer) = self.env_answer.as_ref().filter(|_| round == 0) {
+            builder = builder.word(answer);
+        }
+        if let Some(lists) = word_lists {
+            builder = if self.phrases.is_some() {
+                builder.phrases(lists.answers.clone())
+            } else {
+                builder
+                    .answers(lists.answers.clone())
+                    .dictionary(lists.valid_guesses.clone())
+                    .weights(lists.weights.clone())
             };
-        });
-        guess_array
+        }
+        builder
     }
+}
 
-    fn display_guesses(&mut self) {
-        self.guesses
-            .iter()
-            .enumerate()
-            .for_each(|(guess_number, guess)| {
-                print!("{}: ", guess_number + 1);
-                for elem in guess {
-                    print!("{elem} ");
-                }
-                println!();
-            });
+fn main() {
+    let config = Config::default_path().map_or_else(Config::default, |path| {
+        Config::load(&path).unwrap_or_else(|e| {
+            eprintln!("Couldn't read your config, using the built-in defaults: {e}");
+            Config::default()
+        })
+    });
+    let mut options = Options::parse(std::env::args().skip(1), &config);
+    options.render_mode = RenderMode::detect(options.render_mode);
+    if options.render_mode.uses_color() && !enable_ansi_colors() {
+        options.render_mode = RenderMode::Symbols;
+    }
+    options.theme = options.theme.with_env_overrides();
+    if options.json || !options.render_mode.uses_color() {
+        colored::control::set_override(false);
+    }
+    if options.show_stats {
+        print_stats();
+        return;
+    }
+    if options.status_line {
+        print_status_line();
+        return;
+    }
+    if options.list_themes || options.list_modes {
+        print_lists(&options);
+        return;
+    }
+    if let Some(pattern) = &options.pattern {
+        options.length = pattern.chars().count();
+    }
+    options.infer_length();
+    if options.dict_info {
+        print_dict_info(&options);
+        return;
     }
 
-    fn display_invalid_letters(&self) {
-        if !self.guessed_letters.is_empty() {
-            print!("Letters not in the word: ");
-            self.guessed_letters
-                .iter()
-                .for_each(|letter| print!("{letter} "));
-            println!();
+    let word_lists = options
+        .load_word_lists()
+        .unwrap_or_else(|e| exit_with_error(&e));
+    options.env_answer = env_answer(&options, word_lists.as_ref());
+
+    if let Some(pattern) = &options.pattern {
+        let game = options
+            .new_game(0, word_lists.as_ref())
+            .unwrap_or_else(|e| exit_with_error(&e));
+        for word in game.matching(pattern) {
+            println!("{word}");
         }
+        return;
     }
 
-    fn ask_for_guess(&mut self) -> String {
+    if options.best_opener {
+        print_best_openers(&options, word_lists.as_ref());
+        return;
+    }
+
+    if let Some(games) = options.tournament {
+        play_tournament(&options, games, word_lists.as_ref()).exit();
+    }
+
+    if options.dordle {
+        play_dordle(&options, word_lists.as_ref()).exit();
+    }
+
+    if options.auto {
+        let mut game = options
+            .new_game(0, word_lists.as_ref())
+            .unwrap_or_else(|e| exit_with_error(&e));
+        if options.explain {
+            print_openings(&game);
+        }
+        autoplay(&mut game, options.render_mode, options.theme).exit();
+    }
+
+    let stats_path = Stats::default_path();
+    let mut stats = load_stats(stats_path.as_deref());
+    let leaderboard_path = Leaderboard::default_path();
+    let mut leaderboard = load_leaderboard(leaderboard_path.as_deref());
+
+    let mut used_words = HashSet::new();
+    for round in 0.. {
+        let outcome = play_one_game(
+            &options,
+            round,
+            word_lists.as_ref(),
+            &mut used_words,
+            &mut stats,
+            &mut leaderboard,
+        );
+        let finished = outcome != Outcome::Quit;
+
+        if finished && !options.json {
+            print!("{}", stats.render());
+            print!("{}", leaderboard.render(leaderboard::SHOWN_ENTRIES));
+        }
+        if let Some(path) = &stats_path {
+            if let Err(e) = stats.save(path) {
+                eprintln!("Couldn't save your stats: {e}");
+            }
+        }
+        if let Some(path) = &leaderboard_path {
+            if let Err(e) = leaderboard.save(path) {
+                eprintln!("Couldn't save the leaderboard: {e}");
+            }
+        }
+
+        // Saved even when the player quits, so a mulligan can't be used
+        // twice in a day by quitting after it.
+        if !finished || !options.practice || !ask_play_again() {
+            outcome.exit();
+        }
+    }
+}
+
+/// The frequency of each of `answers` in `entries`, or zero if it has none.
+fn frequencies(answers: &[String], entries: &[DictionaryEntry]) -> Vec<f64> {
+    let frequencies: HashMap<&str, u64> = entries
+        .iter()
+        .filter_map(|entry| Some((entry.word.as_str(), entry.frequency?)))
+        .collect();
+    #[allow(clippy::cast_precision_loss)]
+    answers
+        .iter()
+        .map(|word| frequencies.get(word.as_str()).map_or(0.0, |&f| f as f64))
+        .collect()
+}
+
+/// Reads the saved stats, or starts fresh if there are none or they can't
+/// be read.
+fn load_stats(path: Option<&Path>) -> Stats {
+    path.map_or_else(Stats::default, |path| {
+        Stats::load(path).unwrap_or_else(|e| {
+            eprintln!("Couldn't read your stats, starting fresh: {e}");
+            Stats::default()
+        })
+    })
+}
+
+/// Reads the saved leaderboard, or starts fresh if there is none or it
+/// can't be read.
+fn load_leaderboard(path: Option<&Path>) -> Leaderboard {
+    path.map_or_else(Leaderboard::default, |path| {
+        Leaderboard::load(path).unwrap_or_else(|e| {
+            eprintln!("Couldn't read the leaderboard, starting fresh: {e}");
+            Leaderboard::default()
+        })
+    })
+}
+
+/// The word fixed with `RUSTLE_ANSWER` for a scripted demo, if it is set
+/// and the game could be played with it; if not, says why on stderr. It is
+/// ignored with `--answer`, and by the daily game, seeded games and
+/// tournaments, which pick their own words.
+fn env_answer(options: &Options, word_lists: Option<&WordLists>) -> Option<String> {
+    if options.answer.is_some()
+        || options.daily
+        || options.seed.is_some()
+        || options.tournament.is_some()
+    {
+        return None;
+    }
+    let answer = std::env::var(ANSWER_VAR)
+        .ok()
+        .filter(|answer| !answer.trim().is_empty())?;
+    match options.game_builder(0, word_lists).word(&answer).build() {
+        Ok(game) => Some(game.word().to_string()),
+        Err(e) => {
+            eprintln!("Not using {ANSWER_VAR}={answer}: {e}");
+            None
+        }
+    }
+}
+
+/// Plays `games` games in a row, or lets the solver play them with `--auto`,
+/// and prints how each went. With `--seed` the words are the same every
+/// time. The games are kept out of the saved stats and the leaderboard.
+/// Returns the outcome of the last game played.
+fn play_tournament(options: &Options, games: usize, word_lists: Option<&WordLists>) -> Outcome {
+    let mut tournament = Tournament::default();
+    let mut outcome = Outcome::Quit;
+    for round in 0..games {
+        let mut game = options
+            .new_game(round as u64, word_lists)
+            .unwrap_or_else(|e| exit_with_error(&e));
+        println!("{}", format!("Game {} of {games}", round + 1).cyan());
+        outcome = if options.auto {
+            autoplay(&mut game, options.render_mode, options.theme)
+        } else {
+            let finished = play(&mut game, None);
+            Outcome::of(&game, finished)
+        };
+        if outcome == Outcome::Quit {
+            break;
+        }
+        log_game(options, &game);
+        tournament.record(&game);
+    }
+    print!("{}", tournament.render());
+    outcome
+}
+
+/// Plays [`DORDLE_BOARDS`] words at once for `--dordle`. These games aren't
+/// added to the stats.
+fn play_dordle(options: &Options, word_lists: Option<&WordLists>) -> Outcome {
+    let game = options
+        .new_game(0, word_lists)
+        .unwrap_or_else(|e| exit_with_error(&e));
+    let mut rng = options
+        .seed
+        .map_or_else(RandomNumberGenerator::new, RandomNumberGenerator::seeded);
+    let mut boards = Boards::new(game, DORDLE_BOARDS, &mut rng);
+    let stdin = std::io::stdin();
+    loop {
+        boards.display_guesses();
         println!(
             "{}",
-            format!("Enter your word guess ({WORD_LENGTH} letters) and press ENTER").cyan()
+            format!("Guess {} of {}.", boards.tries() + 1, boards.max_tries()).cyan()
         );
-        self.display_invalid_letters();
-        let mut guess = String::new();
-        let mut valid_guess = false;
-        while !valid_guess {
-            guess = String::new();
-            std::io::stdin().read_line(&mut guess).unwrap();
-            guess = sanitize_word(&guess);
-            if guess.len() != WORD_LENGTH {
-                println!(
-                    "{}",
-                    format!("Your guess must be {WORD_LENGTH} letters.").red()
-                );
-            } else if !self.dictionary.iter().any(|word| word == &guess) {
-                println!(
-                    "{}",
-                    format!("{guess} isn't in the Rustle dictionary.").red()
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            return Outcome::Quit;
+        }
+        match line.trim() {
+            QUIT_COMMAND => return Outcome::Quit,
+            REVEAL_COMMAND => {
+                let message = boards.revealed_words().map_or_else(
+                    || "You gave up.".to_string(),
+                    |words| format!("The words were {words}"),
                 );
-            } else {
-                let c_string = self.colorize_guess(&guess);
-                self.guesses.push(c_string);
-                valid_guess = true;
+                println!("{}", message.bright_red());
+                return Outcome::Lost;
             }
+            input => match boards.submit(input) {
+                Ok(GameStatus::InProgress) => {}
+                Ok(GameStatus::Won { tries }) => {
+                    boards.display_guesses();
+                    println!("Correct! You found every word in {tries} tries.");
+                    return Outcome::Won;
+                }
+                Ok(GameStatus::Lost { .. }) => {
+                    boards.display_guesses();
+                    println!("{}", boards.loss_message().bright_red());
+                    return Outcome::Lost;
+                }
+                Err(invalid) => println!("{}", invalid.to_string().red()),
+            },
+        }
+    }
+}
+
+/// Prints how many words of each length the `--dict` file, or else the
+/// embedded dictionary, has, for `--dict-info`.
+fn print_dict_info(options: &Options) {
+    let words = match options.dictionary.as_deref() {
+        None => rustle::builtin_words(),
+        Some(path) => {
+            let text = if path == STDIN_PATH {
+                std::io::read_to_string(std::io::stdin().lock())
+            } else {
+                std::fs::read_to_string(path)
+            };
+            let text = text.unwrap_or_else(|e| {
+                eprintln!("{}", format!("Couldn't read dictionary {path}: {e}").red());
+                std::process::exit(EXIT_ERROR);
+            });
+            rustle::dictionary_words(&text, options.charset)
+        }
+    };
+    let histogram = rustle::length_histogram(&words);
+    println!("Letters  Words");
+    for (length, count) in &histogram {
+        println!("{length:>7}  {count}");
+    }
+    println!("Total: {}", words.len());
+    println!(
+        "Playing with {}-letter words: {}",
+        options.length,
+        histogram.get(&options.length).unwrap_or(&0)
+    );
+}
+
+/// Prints the saved statistics, for `--stats`.
+fn print_stats() {
+    let stats = Stats::default_path().map_or_else(Stats::default, |path| {
+        Stats::load(&path).unwrap_or_else(|e| {
+            eprintln!("{}", format!("Couldn't read your stats: {e}").red());
+            std::process::exit(EXIT_ERROR);
+        })
+    });
+    if stats.games_played == 0 {
+        println!("No games played yet.");
+    } else {
+        print!("{}", stats.render());
+    }
+}
+
+/// Prints the values that flags accept, for `--list-themes` and
+/// `--list-modes`.
+fn print_lists(options: &Options) {
+    if options.list_themes {
+        print!("{}", help::themes());
+    }
+    if options.list_themes && options.list_modes {
+        println!();
+    }
+    if options.list_modes {
+        print!("{}", help::modes());
+    }
+}
+
+/// Prints a summary of the game in progress, for `--status-line`, or
+/// nothing if there is none.
+fn print_status_line() {
+    let saved = SavedGame::default_path().map_or(Ok(None), |path| SavedGame::load(&path));
+    match saved {
+        Ok(Some(saved)) => println!("{}", saved.status_line()),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("Couldn't read the game in progress: {e}");
+            std::process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+/// Keeps the state of `game` on disk while it is being played, so
+/// `--status-line` can show it, and removes it once the game is over.
+fn save_progress(game: &RustleGame) {
+    if game.check_status() != GameStatus::InProgress {
+        clear_progress();
+        return;
+    }
+    if let Some(path) = SavedGame::default_path() {
+        if let Err(e) = game.save(&path) {
+            eprintln!("Couldn't save the game in progress: {e}");
+        }
+    }
+}
+
+/// Loads the game left off last time, for `--resume`, with the word lists
+/// and settings of `builder`. Returns `None` to start a new game if there is
+/// none or it can't be loaded.
+fn resume_game(builder: &RustleGameBuilder) -> Option<RustleGame> {
+    let path = SavedGame::default_path()?;
+    match RustleGame::load(&path, builder) {
+        Ok(Some(game)) => {
+            println!("{}", "Picking up where you left off.".cyan());
+            Some(game)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            eprintln!("Couldn't resume the last game, starting a new one: {e}");
+            None
         }
-        guess
     }
+}
+
+/// Removes the saved game in progress, once it is over.
+fn clear_progress() {
+    if let Some(path) = SavedGame::default_path() {
+        if let Err(e) = SavedGame::clear(&path) {
+            eprintln!("Couldn't remove the saved game: {e}");
+        }
+    }
+}
+
+/// Adds the points of `game` to `name`'s total if it was won, returning
+/// them. Zen games don't score, since they can't be lost.
+fn award_points(game: &RustleGame, name: &str, leaderboard: &mut Leaderboard) -> Option<u32> {
+    let GameStatus::Won { tries } = game.check_status() else {
+        return None;
+    };
+    if game.zen() {
+        return None;
+    }
+    let points = score_game(tries, game.elapsed()?, game.hard_mode());
+    leaderboard.record(name, points);
+    Some(points)
+}
+
+/// Prints how well opening guesses narrow down the word of `game`, for
+/// `--explain`.
+fn print_openings(game: &RustleGame) {
+    let openings = game.explain_openings(solver::EXPLAIN_SAMPLE);
+    println!(
+        "{}",
+        format!(
+            "Words ruled out for {} by {} opening guesses, best first:",
+            game.word(),
+            openings.len()
+        )
+        .dimmed()
+    );
+    for opening in &openings {
+        println!(
+            "{} {} (expected {:.1})",
+            opening.guess, opening.eliminated, opening.expected
+        );
+    }
+}
 
-    fn is_game_over(&self, guess: &str) -> bool {
-        let n_tries = self.guesses.len();
-        if guess == self.word {
-            println!("Correct! You guessed the word in {n_tries} tries.");
-            true
-        } else if n_tries >= MAX_TRIES {
+/// Prints the best opening guesses for the answers, for `--best-opener`.
+/// They are cached, so asking again for the same word list and sample is
+/// instant.
+fn print_best_openers(options: &Options, word_lists: Option<&WordLists>) {
+    let game = options
+        .new_game(0, word_lists)
+        .unwrap_or_else(|e| exit_with_error(&e));
+    let path = Openers::default_path();
+    let key = Openers::key(&game, options.sample);
+    let cached = path.as_deref().and_then(|path| {
+        Openers::load(path).unwrap_or_else(|e| {
+            eprintln!("Couldn't read the cached openers: {e}");
+            None
+        })
+    });
+    let openers = cached
+        .filter(|openers| openers.matches(&key))
+        .unwrap_or_else(|| {
             println!(
                 "{}",
-                format!("You ran out of tries! The word was {}", self.word).bright_red()
+                "Rating every answer as an opener, this can take a while...".dimmed()
             );
-            true
-        } else {
-            false
+            let openers = Openers::compute(&game, options.sample);
+            if let Some(path) = &path {
+                if let Err(e) = openers.save(path) {
+                    eprintln!("Couldn't cache the openers: {e}");
+                }
+            }
+            openers
+        });
+    print!("{}", openers.render());
+}
+
+/// Sets up and plays a whole game, recording the result in `stats` and the
+/// points in `leaderboard`. Unless repeats are allowed, the word is one not
+/// in `used_words`, which it is then added to.
+fn play_one_game(
+    options: &Options,
+    round: u64,
+    word_lists: Option<&WordLists>,
+    used_words: &mut HashSet<String>,
+    stats: &mut Stats,
+    leaderboard: &mut Leaderboard,
+) -> Outcome {
+    let mut builder = options.game_builder(round, word_lists);
+    if !options.repeats {
+        builder = builder.used_words(std::mem::take(used_words));
+    }
+    let resumed = (options.resume && round == 0)
+        .then(|| resume_game(&builder))
+        .flatten();
+    let mut game =
+        resumed.unwrap_or_else(|| builder.build().unwrap_or_else(|e| exit_with_error(&e)));
+    used_words.clone_from(game.used_words());
+    if options.daily {
+        game = game
+            .with_daily(true)
+            .with_mulligan(stats.mulligan_available(rustle::today()));
+    }
+    if options.explain {
+        print_openings(&game);
+    }
+    if options.json {
+        let finished = play_json(&mut game, stats);
+        record_mulligan(&game, stats);
+        if finished {
+            log_game(options, &game);
+        }
+        award_points(&game, &options.name, leaderboard);
+        return Outcome::of(&game, finished);
+    }
+    let finished = play(&mut game, Some(stats));
+    record_mulligan(&game, stats);
+    if !finished {
+        return Outcome::Quit;
+    }
+    log_game(options, &game);
+    if let Some(points) = award_points(&game, &options.name, leaderboard) {
+        println!("{} scored {points} points.", options.name);
+    }
+    if options.share {
+        println!("\n{}", game.share_grid());
+        if options.daily {
+            println!("Proof: {}", game.result_hash(rustle::today()));
+        }
+        println!();
+    }
+    if matches!(game.check_status(), GameStatus::Won { .. }) {
+        if options.explore {
+            explore(&game, options.render_mode, options.theme);
+        }
+        return Outcome::Won;
+    }
+    // Replays are only practice: the word is already lost, so they aren't
+    // recorded, scored or shared, and the daily game has none.
+    while !options.daily && ask_replay() {
+        game = game.replay();
+        if !play(&mut game, None) {
+            return Outcome::Quit;
+        }
+        log_game(options, &game);
+        if matches!(game.check_status(), GameStatus::Won { .. }) {
+            if options.explore {
+                explore(&game, options.render_mode, options.theme);
+            }
+            break;
+        }
+    }
+    Outcome::Lost
+}
+
+/// Appends the transcript of the finished `game` to the `--log` file.
+fn log_game(options: &Options, game: &RustleGame) {
+    if let Some(path) = &options.log {
+        if let Err(e) = Transcript::of(game).append_to(Path::new(path)) {
+            eprintln!("Couldn't log the game to {path}: {e}");
         }
     }
 }
 
-fn create_charmap(word: &str) -> HashMap<char, i32> {
-    let mut charmap: HashMap<char, i32> = HashMap::new();
-    word.chars().for_each(|c| {
-        let count = charmap.entry(c).or_insert(0);
-        *count += 1;
+/// Remembers that today's mulligan is gone if it was used in `game`.
+fn record_mulligan(game: &RustleGame, stats: &mut Stats) {
+    if game.mulligan_used() {
+        stats.record_mulligan(rustle::today());
+    }
+}
+
+/// Once a word list has been read from stdin, guesses have to come from
+/// somewhere else: point stdin at the controlling terminal instead. Exits
+/// if that fails, since the game would only read the end of the input.
+#[cfg(unix)]
+fn reattach_stdin_to_terminal() {
+    use std::os::fd::AsRawFd;
+
+    let tty = std::fs::File::open("/dev/tty").unwrap_or_else(|e| {
+        eprintln!("Couldn't open the terminal to read guesses from: {e}");
+        std::process::exit(EXIT_ERROR);
     });
-    charmap
+    // SAFETY: both descriptors are valid for the duration of the call, and
+    // dup2 leaves the terminal open as fd 0 after `tty` is dropped.
+    if unsafe { libc::dup2(tty.as_raw_fd(), libc::STDIN_FILENO) } == -1 {
+        let e = std::io::Error::last_os_error();
+        eprintln!("Couldn't read guesses from the terminal: {e}");
+        std::process::exit(EXIT_ERROR);
+    }
+}
+
+#[cfg(not(unix))]
+fn reattach_stdin_to_terminal() {
+    eprintln!("Guesses can't be read once the word list has been read from stdin.");
+}
+
+/// Older Windows consoles only understand ANSI color codes once virtual
+/// terminal processing is turned on. Returns whether colors will show.
+#[cfg(windows)]
+fn enable_ansi_colors() -> bool {
+    colored::control::set_virtual_terminal(true).is_ok()
+}
+
+#[cfg(not(windows))]
+fn enable_ansi_colors() -> bool {
+    true
 }
 
-fn is_color(c: &ColoredString, col: Color) -> bool {
-    if *c == c.clone().color(col) {
+/// After a win, lets the player try more words to see how they would have
+/// scored. They aren't played, so the result and the stats stay as they
+/// are.
+fn explore(game: &RustleGame, render_mode: RenderMode, theme: Theme) {
+    println!("{}", "Keep exploring? (y/n)".cyan());
+    let stdin = std::io::stdin();
+    let mut answer = String::new();
+    if stdin.read_line(&mut answer).unwrap_or(0) == 0
+        || !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+    {
+        return;
+    }
+    loop {
+        println!(
+            "{}",
+            "Enter a word to see how it scores, or press ENTER to stop.".cyan()
+        );
+        let mut line = String::new();
+        if stdin.read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+            return;
+        }
+        match game.explore(&line) {
+            Ok(guess) if game.mode() == Mode::Distance => {
+                let distance = game.distance(&guess.word);
+                println!("{}", rustle::render_distance_row(&guess.word, distance));
+            }
+            Ok(guess) => println!("{}", render_guess(&guess, render_mode, theme)),
+            Err(invalid) => println!("{}", invalid.to_string().red()),
+        }
+    }
+}
+
+fn ask_replay() -> bool {
+    println!(
+        "{}",
+        format!("Type {REPLAY_COMMAND} to try the same word again, or press ENTER to go on.")
+            .cyan()
+    );
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).unwrap_or(0) > 0 && answer.trim() == REPLAY_COMMAND
+}
+
+fn ask_play_again() -> bool {
+    println!("{}", "Play again? (y/n)".cyan());
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer).unwrap_or(0) > 0
+        && matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Records a win in `tries` guesses in `stats`, apart from the other games
+/// in zen mode.
+fn record_win(stats: &mut Stats, game: &RustleGame, tries: usize) {
+    if game.zen() {
+        stats.record_zen_win(tries);
+    } else {
+        stats.record_win(tries);
+    }
+}
+
+/// Records a loss in `stats`. Giving up in zen mode isn't recorded, since a
+/// zen game can't be lost.
+fn record_loss(stats: &mut Stats, game: &RustleGame) {
+    if !game.zen() {
+        stats.record_loss();
+    }
+}
+
+/// Plays `game` until it is won, lost or given up, recording the result in
+/// `stats`. Returns `false` if the player quit instead. A game without
+/// `stats`, like a replay or a tournament round, is neither recorded nor
+/// saved for `--resume`, and neither is the daily game, which can't be
+/// resumed.
+fn play(game: &mut RustleGame, mut stats: Option<&mut Stats>) -> bool {
+    let saved = stats.is_some() && !game.is_daily();
+    loop {
+        game.display_guesses();
+        let action = game.ask_for_guess();
+        if saved {
+            save_progress(game);
+        }
+        match action {
+            GuessAction::Word(_) => match game.check_status() {
+                GameStatus::InProgress => {}
+                GameStatus::Won { tries } => {
+                    game.display_guesses();
+                    println!("Correct! You guessed the word in {tries} tries.");
+                    if let Some(elapsed) = game.elapsed() {
+                        println!("Time: {}", format_duration(elapsed));
+                    }
+                    if let Some(stats) = &mut stats {
+                        record_win(stats, game, tries);
+                        if let Some(elapsed) = game.elapsed().filter(|_| !game.zen()) {
+                            stats.record_time(elapsed);
+                        }
+                    }
+                    return true;
+                }
+                GameStatus::Lost { .. } => {
+                    game.display_guesses();
+                    println!("{}", game.loss_message().bright_red());
+                    if let Some(stats) = &mut stats {
+                        stats.record_loss();
+                    }
+                    return true;
+                }
+            },
+            GuessAction::Reveal => {
+                if saved {
+                    clear_progress();
+                }
+                let message = game.revealed_word().map_or_else(
+                    || "You gave up.".to_string(),
+                    |word| format!("The word was {word}"),
+                );
+                println!("{}", message.bright_red());
+                if let Some(stats) = &mut stats {
+                    record_loss(stats, game);
+                }
+                return true;
+            }
+            GuessAction::Quit => return false,
+        }
+    }
+}
+
+/// Plays `game` for a frontend: guesses are read one per line from stdin,
+/// and every scored guess, rejected guess and the result are printed as a
+/// line of JSON. Returns `false` if the player quit or stdin was closed.
+fn play_json(game: &mut RustleGame, stats: &mut Stats) -> bool {
+    for line in std::io::stdin().lock().lines() {
+        let Ok(line) = line else { return false };
+        match line.trim() {
+            QUIT_COMMAND => return false,
+            REVEAL_COMMAND => {
+                println!("{}", json_loss(game.revealed_word()));
+                record_loss(stats, game);
+                return true;
+            }
+            _ => {}
+        }
+
+        let result = match game.submit(&line) {
+            Ok(result) => result,
+            Err(invalid) => {
+                println!("{}", json_error(&invalid.to_string()));
+                continue;
+            }
+        };
+        let guesses = game.guesses();
+        println!("{}", json_guess(&guesses[guesses.len() - 1], guesses.len()));
+        if result == GameStatus::InProgress {
+            continue;
+        }
+        if let GameStatus::Lost { .. } = result {
+            println!("{}", json_loss(game.revealed_word()));
+        } else {
+            println!("{}", json_result(&result));
+        }
+        if let GameStatus::Won { tries } = result {
+            record_win(stats, game, tries);
+        } else {
+            record_loss(stats, game);
+        }
         return true;
     }
     false
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_sanitize_word() {
-        assert_eq!(sanitize_word("hello"), "HELLO");
-        assert_eq!(sanitize_word("hello world"), "HELLOWORLD");
-    }
-
-    #[test]
-    fn test_is_color() {
-        let c = "a".bright_green();
-        assert!(is_color(&c, Color::BrightGreen));
-        assert!(!is_color(&c, Color::Blue));
-    }
-
-    #[test]
-    fn test_create_charmap() {
-        let word = "hello";
-        let charmap = create_charmap(word);
-        assert_eq!(charmap[&'h'], 1);
-        assert_eq!(charmap[&'e'], 1);
-        assert_eq!(charmap[&'l'], 2);
-        assert_eq!(charmap[&'o'], 1);
-    }
-
-    #[test]
-    fn test_colorize_guess_all_correct() {
-        let mut game = RustleGame::new();
-        game.word = "ABC".to_string();
-        let colored_guess = game.colorize_guess(&("ABC".to_string()));
-        assert_eq!(
-            colored_guess,
-            (vec![
-                "A".color(CORRECT_COLOR),
-                "B".color(CORRECT_COLOR),
-                "C".color(CORRECT_COLOR)
-            ])
-        );
-        assert_eq!(game.guessed_letters, (vec![]).into_iter().collect());
-    }
-
-    #[test]
-    fn test_colorize_guess_all_incorrect() {
-        let mut game = RustleGame::new();
-        game.word = "ABC".to_string();
-        let colored_guess = game.colorize_guess(&("DEF".to_string()));
-        assert_eq!(
-            colored_guess,
-            (vec![
-                "D".color(INCORRECT_COLOR),
-                "E".color(INCORRECT_COLOR),
-                "F".color(INCORRECT_COLOR)
-            ])
+fn autoplay(game: &mut RustleGame, render_mode: RenderMode, theme: Theme) -> Outcome {
+    let result = game.autoplay();
+    for (number, guess) in game.guesses().iter().enumerate() {
+        println!(
+            "{}: {}",
+            number + 1,
+            render_guess(guess, render_mode, theme)
         );
-        assert_eq!(
-            game.guessed_letters,
-            (vec!['D', 'E', 'F'].into_iter().collect())
+    }
+    if result.won {
+        println!("Solved in {} tries.", result.tries);
+        Outcome::Won
+    } else {
+        let message = game.revealed_word().map_or_else(
+            || "Failed to solve it!".to_string(),
+            |word| format!("Failed to solve it! The word was {word}"),
         );
+        println!("{}", message.bright_red());
+        Outcome::Lost
     }
+}
 
-    #[test]
-    fn test_colorize_guess_two_misplaced() {
-        let mut game = RustleGame::new();
-        game.word = "ABC".to_string();
-        let colored_guess = game.colorize_guess(&("ACB".to_string()));
-        assert_eq!(
-            colored_guess,
-            (vec![
-                "C".color(CORRECT_COLOR),
-                "B".color(MISPLACED_COLOR),
-                "A".color(MISPLACED_COLOR)
-            ])
-        );
-        assert_eq!(game.guessed_letters, (vec!['B', 'A'].into_iter().collect()));
-    }
-
-    #[test]
-    fn test_colorize_guess_one_correct_one_misplaced() {
-        let mut game = RustleGame::new();
-        game.word = "ABC".to_string();
-        let colored_guess = game.colorize_guess(&("ACD".to_string()));
-        assert_eq!(
-            colored_guess,
-            (vec![
-                "A".color(CORRECT_COLOR),
-                "C".color(MISPLACED_COLOR),
-                "D".color(INCORRECT_COLOR)
-            ])
-        );
-        assert_eq!(game.guessed_letters, (vec!['D'].into_iter().collect()));
+fn exit_with_error(error: &GameError) -> ! {
+    eprintln!("{}", format!("Couldn't start the game: {error}").red());
+    std::process::exit(EXIT_ERROR);
+}
+
+fn parse_number(flag: &str, value: Option<&str>) -> usize {
+    match value.map(str::parse) {
+        Some(Ok(n)) if n > 0 => n,
+        _ => exit_with_usage(&format!("{flag} expects a positive number")),
     }
 }
+
+fn parse_seed(value: Option<&str>) -> u64 {
+    match value.map(str::parse) {
+        Some(Ok(seed)) => seed,
+        _ => exit_with_usage("--seed expects a number"),
+    }
+}
+
+fn parse_difficulty(value: Option<&str>) -> Difficulty {
+    value
+        .and_then(Difficulty::from_name)
+        .unwrap_or_else(|| exit_with_usage("--difficulty expects easy, medium or hard"))
+}
+
+fn parse_theme(value: Option<&str>) -> Theme {
+    match value {
+        Some(name) => Theme::from_name_or_default(name),
+        None => exit_with_usage(&format!(
+            "--theme expects one of {}",
+            Theme::NAMES.join(", ")
+        )),
+    }
+}
+
+fn parse_mode(value: Option<&str>) -> Mode {
+    value.and_then(Mode::from_name).unwrap_or_else(|| {
+        exit_with_usage(&format!("--mode expects one of {}", Mode::NAMES.join(", ")))
+    })
+}
+
+fn parse_numbering(value: Option<&str>) -> Numbering {
+    value.and_then(Numbering::from_name).unwrap_or_else(|| {
+        exit_with_usage(&format!(
+            "--numbering expects one of {}",
+            Numbering::NAMES.join(", ")
+        ))
+    })
+}
+
+fn parse_word(value: Option<String>) -> String {
+    value.unwrap_or_else(|| exit_with_usage("--answer expects a word"))
+}
+
+fn parse_pattern(value: Option<String>) -> String {
+    value
+        .filter(|pattern| !pattern.trim().is_empty())
+        .map_or_else(
+            || exit_with_usage("--match expects a pattern like CR.N."),
+            |pattern| pattern.trim().to_string(),
+        )
+}
+
+fn parse_name(value: Option<String>) -> String {
+    value
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| exit_with_usage("--name expects a player name"))
+}
+
+/// The player's login name, so points are kept apart without `--name`.
+fn default_name() -> String {
+    ["USER", "USERNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|name| !name.is_empty()))
+        .unwrap_or_else(|| "player".to_string())
+}
+
+fn parse_path(flag: &str, value: Option<String>) -> String {
+    value.unwrap_or_else(|| exit_with_usage(&format!("{flag} expects a file path")))
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{message}");
+    eprintln!("{USAGE}");
+    std::process::exit(EXIT_ERROR);
+}
@@ -0,0 +1,186 @@
+//! The best opening guesses for a word list, kept between runs since they
+//! are slow to work out.
+
+use crate::json::{self, Value};
+use crate::solver::{self, Opener};
+use crate::stats::config_dir;
+use crate::{Fnv1a, RustleGame};
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// How many openers are kept and shown.
+pub const SHOWN_OPENERS: usize = 10;
+
+/// The best openers for one word list, with a key telling which list and
+/// sample they were worked out for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Openers {
+    key: String,
+    /// How many answers the openers were rated against.
+    pub answers: usize,
+    pub ranked: Vec<Opener>,
+}
+
+impl Openers {
+    /// Works out the best openers for the answers of `game`, as
+    /// [`RustleGame::best_openers`] does. This is slow for big word lists.
+    #[must_use]
+    pub fn compute(game: &RustleGame, sample: Option<usize>) -> Openers {
+        let answers = game.possible_answers();
+        let mut ranked = solver::best_openers(&answers, sample);
+        let rated = ranked.len();
+        ranked.truncate(SHOWN_OPENERS);
+        Openers {
+            key: Self::key(game, sample),
+            answers: rated,
+            ranked,
+        }
+    }
+
+    /// Identifies the answers of `game` and the sample size, so cached
+    /// openers are only reused for the same word list.
+    #[must_use]
+    pub fn key(game: &RustleGame, sample: Option<usize>) -> String {
+        let mut hash = Fnv1a::default();
+        for word in game.possible_answers() {
+            hash.write(word.as_bytes());
+            hash.write(&[0]);
+        }
+        let sample = sample.map_or_else(|| "all".to_string(), |sample| sample.to_string());
+        format!("{:016x}-{sample}", hash.0)
+    }
+
+    /// Whether these openers were worked out for `key`.
+    #[must_use]
+    pub fn matches(&self, key: &str) -> bool {
+        self.key == key
+    }
+
+    /// A numbered list of the openers, best first.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut out = format!(
+            "Best openers over {} answers, by the words they leave on average:\n",
+            self.answers
+        );
+        let number_width = self.ranked.len().to_string().len();
+        for (i, opener) in self.ranked.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "{:>number_width$}. {}  {:.1}",
+                i + 1,
+                opener.guess,
+                opener.remaining
+            );
+        }
+        out
+    }
+
+    /// Where the openers are cached: `rustle/openers.json` in the user's
+    /// config directory, if one can be found.
+    #[must_use]
+    pub fn default_path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("rustle").join("openers.json"))
+    }
+
+    /// Reads cached openers from `path`, or `None` if there are none yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't valid openers
+    /// JSON.
+    pub fn load(path: &Path) -> io::Result<Option<Openers>> {
+        json::load_file(path, "valid openers", Self::from_json)
+    }
+
+    /// Writes the openers to `path`, creating its directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file or its directory can't be written.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        json::save_file(path, &self.to_json())
+    }
+
+    fn to_json(&self) -> Value {
+        let ranked: Vec<Value> = self
+            .ranked
+            .iter()
+            .map(|opener| {
+                json::object([
+                    ("guess", opener.guess.as_str().into()),
+                    ("remaining", opener.remaining.into()),
+                ])
+            })
+            .collect();
+        json::object([
+            ("key", self.key.as_str().into()),
+            ("answers", (self.answers as u64).into()),
+            ("ranked", ranked.into()),
+        ])
+    }
+
+    fn from_json(text: &str) -> Option<Openers> {
+        let value = json::parse(text)?;
+        let ranked = value
+            .get("ranked")?
+            .as_array()?
+            .iter()
+            .map(|opener| {
+                Some(Opener {
+                    guess: opener.get("guess")?.as_str()?.to_string(),
+                    remaining: opener.get("remaining")?.as_f64()?,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Openers {
+            key: value.get("key")?.as_str()?.to_string(),
+            answers: usize::try_from(value.get("answers")?.as_u64()?).ok()?,
+            ranked,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(words: &[&str]) -> RustleGame {
+        game_of(words[0], words)
+    }
+
+    fn game_of(word: &str, words: &[&str]) -> RustleGame {
+        let words: Vec<String> = words.iter().map(ToString::to_string).collect();
+        RustleGame::replay_of(word.to_string(), words)
+    }
+
+    #[test]
+    fn test_compute_and_render() {
+        let game = game(&["CRANE", "CANES", "CHIRP", "STORY"]);
+        let openers = Openers::compute(&game, None);
+        assert_eq!(openers.answers, 4);
+        assert!(openers.matches(&Openers::key(&game, None)));
+        assert!(!openers.matches(&Openers::key(&game, Some(2))));
+        assert!(openers
+            .render()
+            .starts_with("Best openers over 4 answers, by the words they leave on average:\n1. "));
+    }
+
+    #[test]
+    fn test_key_depends_on_the_words() {
+        let crane = game(&["CRANE", "SLATE"]);
+        let slate = game_of("SLATE", &["CRANE", "SLATE"]);
+        let other = game(&["CRANE", "GRACE"]);
+        assert_eq!(Openers::key(&crane, None), Openers::key(&slate, None));
+        assert_ne!(Openers::key(&crane, None), Openers::key(&other, None));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let openers = Openers::compute(&game(&["CRANE", "SLATE", "GRACE"]), Some(2));
+        let text = openers.to_json().to_string();
+        assert_eq!(Openers::from_json(&text), Some(openers));
+        assert_eq!(Openers::from_json("{}"), None);
+    }
+}
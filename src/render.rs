@@ -0,0 +1,618 @@
+//! Turning scored guesses into text for the terminal.
+
+use crate::json::{self, Value};
+use crate::{GameStatus, Guess, LetterStatus, Theme, PEEK_PLACEHOLDER};
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::io::IsTerminal;
+
+const KEYBOARD_ROWS: [&str; 3] = ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"];
+
+/// How many keys go in each extra row of letters that aren't on a QWERTY
+/// keyboard.
+const EXTRA_ROW_LENGTH: usize = 10;
+
+/// How many columns every letter takes up on the board, so rows line up
+/// whatever their colors.
+pub const CELL_WIDTH: usize = 3;
+
+/// How many columns the terminal is assumed to have when that can't be
+/// found out.
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// Fills the letters of rows that haven't been guessed yet.
+pub const EMPTY_CELL: char = '·';
+
+/// How letter statuses are shown to the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderMode {
+    /// Letters are colored green, yellow or red.
+    #[default]
+    Color,
+    /// Letters are wrapped in symbols, `[A]` for correct, `(A)` for
+    /// misplaced and ` A ` for absent, so no color vision is needed.
+    Symbols,
+    /// Every guess is described in a plain sentence, such as `C correct,
+    /// R absent, ...`, for screen readers.
+    Describe,
+}
+
+impl RenderMode {
+    /// Every mode, in the order they are listed.
+    pub const ALL: [RenderMode; 3] = [RenderMode::Color, RenderMode::Symbols, RenderMode::Describe];
+
+    /// The names accepted by [`RenderMode::from_name`], in the order of
+    /// [`RenderMode::ALL`].
+    pub const NAMES: [&'static str; 3] = [
+        Self::ALL[0].name(),
+        Self::ALL[1].name(),
+        Self::ALL[2].name(),
+    ];
+
+    /// Looks up a mode by name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<RenderMode> {
+        Self::ALL.into_iter().find(|value| value.name() == name)
+    }
+
+    /// The name [`RenderMode::from_name`] looks this mode up by.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            RenderMode::Color => "color",
+            RenderMode::Symbols => "symbols",
+            RenderMode::Describe => "describe",
+        }
+    }
+
+    /// Picks the mode to actually use: the requested one, unless colors were
+    /// requested but stdout isn't a terminal or `NO_COLOR` is set, in which
+    /// case colors would only clutter the output and symbols are used
+    /// instead.
+    #[must_use]
+    pub fn detect(requested: RenderMode) -> RenderMode {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+        Self::resolve(requested, no_color, std::io::stdout().is_terminal())
+    }
+
+    fn resolve(requested: RenderMode, no_color: bool, is_tty: bool) -> RenderMode {
+        if requested == RenderMode::Color && (no_color || !is_tty) {
+            RenderMode::Symbols
+        } else {
+            requested
+        }
+    }
+
+    /// Whether this mode prints ANSI color codes.
+    #[must_use]
+    pub fn uses_color(self) -> bool {
+        self == RenderMode::Color
+    }
+}
+
+/// How the rows of the board and the prompt count guesses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Numbering {
+    /// `1: `, `2: `, ... as people count.
+    #[default]
+    FromOne,
+    /// `0: `, `1: `, ... counting the guesses made before the row.
+    FromZero,
+    /// `Guess 1/6: `, `Guess 2/6: `, ...
+    OfTotal,
+}
+
+impl Numbering {
+    /// Every numbering, in the order they are listed.
+    pub const ALL: [Numbering; 3] = [Numbering::FromOne, Numbering::FromZero, Numbering::OfTotal];
+
+    /// The names accepted by [`Numbering::from_name`], in the order of
+    /// [`Numbering::ALL`].
+    pub const NAMES: [&'static str; 3] = [
+        Self::ALL[0].name(),
+        Self::ALL[1].name(),
+        Self::ALL[2].name(),
+    ];
+
+    /// Looks up a numbering by name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Numbering> {
+        Self::ALL.into_iter().find(|value| value.name() == name)
+    }
+
+    /// The name [`Numbering::from_name`] looks this numbering up by.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Numbering::FromOne => "one",
+            Numbering::FromZero => "zero",
+            Numbering::OfTotal => "total",
+        }
+    }
+
+    /// The number shown for the guess at `index`, counting from 0.
+    #[must_use]
+    pub fn number(self, index: usize) -> usize {
+        match self {
+            Numbering::FromZero => index,
+            Numbering::FromOne | Numbering::OfTotal => index + 1,
+        }
+    }
+}
+
+/// The prefix of the board row at `index` out of `rows`, such as ` 7: ` or
+/// `Guess 7/10: `, where `total` is how many guesses the player gets. The
+/// numbers are right-aligned, so the rows line up.
+#[must_use]
+pub fn row_prefix(numbering: Numbering, index: usize, rows: usize, total: &str) -> String {
+    let width = numbering.number(rows.saturating_sub(1)).to_string().len();
+    let number = numbering.number(index);
+    match numbering {
+        Numbering::FromOne | Numbering::FromZero => format!("{number:>width$}: "),
+        Numbering::OfTotal => format!("Guess {number:>width$}/{total}: "),
+    }
+}
+
+/// Renders one guess as a single line of text, in the colors of `theme` if
+/// `mode` uses color.
+#[must_use]
+pub fn render_guess(guess: &Guess, mode: RenderMode, theme: Theme) -> String {
+    match mode {
+        RenderMode::Color => guess
+            .colorize(theme)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" "),
+        RenderMode::Symbols => guess
+            .word
+            .chars()
+            .zip(&guess.statuses)
+            .map(|(c, status)| match status {
+                _ if c == ' ' => "   ".to_string(),
+                _ if c == PEEK_PLACEHOLDER => format!(" {c} "),
+                LetterStatus::Correct => format!("[{c}]"),
+                LetterStatus::Misplaced => format!("({c})"),
+                LetterStatus::Absent => format!(" {c} "),
+            })
+            .collect(),
+        RenderMode::Describe => describe_guess(&guess.statuses, &guess.word),
+    }
+}
+
+/// A line explaining how letters are shown in `mode` and `theme`, with an
+/// example letter for each status, e.g. `[A] right spot  (B) wrong spot
+/// C not in the word`. `None` when guesses are described in words, which
+/// needs no legend.
+#[must_use]
+pub fn render_legend(mode: RenderMode, theme: Theme) -> Option<String> {
+    if mode == RenderMode::Describe {
+        return None;
+    }
+    let examples = [
+        ('A', LetterStatus::Correct, "right spot"),
+        ('B', LetterStatus::Misplaced, "wrong spot"),
+        ('C', LetterStatus::Absent, "not in the word"),
+    ];
+    let entries: Vec<String> = examples
+        .iter()
+        .map(|&(letter, status, meaning)| {
+            let example = Guess {
+                word: letter.to_string(),
+                statuses: vec![status],
+            };
+            format!("{} {meaning}", render_guess(&example, mode, theme))
+        })
+        .collect();
+    Some(entries.join("   "))
+}
+
+/// Describes how every letter of `guess` scored in a plain sentence, e.g.
+/// `C correct, R absent, A misplaced, N absent, E correct.` Spaces between
+/// the words of a phrase are left out.
+#[must_use]
+pub fn describe_guess(statuses: &[LetterStatus], guess: &str) -> String {
+    let letters: Vec<String> = guess
+        .chars()
+        .zip(statuses)
+        .filter(|(c, _)| *c != ' ')
+        .map(|(c, status)| format!("{c} {}", status.name()))
+        .collect();
+    format!("{}.", letters.join(", "))
+}
+
+/// Renders one guess as a row of the board: like [`render_guess`], but
+/// every letter is centered in a cell [`CELL_WIDTH`] columns wide.
+#[must_use]
+pub fn render_row(guess: &Guess, mode: RenderMode, theme: Theme) -> String {
+    match mode {
+        RenderMode::Color => guess
+            .colorize(theme)
+            .iter()
+            .map(|letter| pad_cell(&letter.to_string()))
+            .collect(),
+        RenderMode::Symbols | RenderMode::Describe => render_guess(guess, mode, theme),
+    }
+}
+
+/// Renders a guess of a [`Mode::Distance`](crate::Mode::Distance) game as
+/// a row of the board: its letters, uncolored, followed by how many edits
+/// away from the word it is.
+#[must_use]
+pub fn render_distance_row(guess: &str, distance: usize) -> String {
+    let letters: String = guess.chars().map(|c| pad_cell(&c.to_string())).collect();
+    let unit = if distance == 1 { "edit" } else { "edits" };
+    format!("{letters}  {distance} {unit} away")
+}
+
+/// Renders a row that hasn't been guessed yet, shaped like `word`: a
+/// [`EMPTY_CELL`] for every letter and a blank cell for every space.
+#[must_use]
+pub fn render_empty_row(word: &str) -> String {
+    word.chars()
+        .map(|c| match c {
+            ' ' => " ".repeat(CELL_WIDTH),
+            _ => pad_cell(&EMPTY_CELL.to_string()),
+        })
+        .collect()
+}
+
+/// Centers `cell` in [`CELL_WIDTH`] columns, not counting its color codes.
+fn pad_cell(cell: &str) -> String {
+    let padding = CELL_WIDTH.saturating_sub(visible_width(cell));
+    let left = padding / 2;
+    format!("{}{cell}{}", " ".repeat(left), " ".repeat(padding - left))
+}
+
+/// How many columns `text` takes up in a terminal: its characters, without
+/// the ANSI escape sequences that color them.
+#[must_use]
+pub fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip to the letter that ends the sequence, e.g. the `m` of
+            // `ESC[1;32m`.
+            chars.find(char::is_ascii_alphabetic);
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+/// How many columns the terminal has: `COLUMNS` if it is set, or else what
+/// the terminal on stdout reports, or else [`DEFAULT_WIDTH`].
+#[must_use]
+pub fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&width| width > 0)
+        .or_else(stdout_width)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+#[cfg(unix)]
+fn stdout_width() -> Option<usize> {
+    // SAFETY: winsize is plain data that ioctl fills in, and stdout stays
+    // open for the duration of the call.
+    unsafe {
+        let mut size: libc::winsize = std::mem::zeroed();
+        if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &raw mut size) != 0
+            || size.ws_col == 0
+        {
+            return None;
+        }
+        Some(usize::from(size.ws_col))
+    }
+}
+
+/// The size of the terminal can't be asked for here.
+#[cfg(not(unix))]
+fn stdout_width() -> Option<usize> {
+    None
+}
+
+/// Renders statuses as the colored squares used to share results, without
+/// giving away any letters.
+#[must_use]
+pub fn emoji_row(statuses: &[LetterStatus]) -> String {
+    statuses
+        .iter()
+        .map(|status| match status {
+            LetterStatus::Correct => '🟩',
+            LetterStatus::Misplaced => '🟨',
+            LetterStatus::Absent => '⬛',
+        })
+        .collect()
+}
+
+/// Renders a guess as a line of JSON for frontends, e.g.
+/// `{"guess":"CRANE","statuses":["correct","absent",...],"tries":2}`.
+#[must_use]
+pub fn json_guess(guess: &Guess, tries: usize) -> String {
+    json::object([
+        ("guess", guess.word.as_str().into()),
+        (
+            "statuses",
+            guess
+                .statuses
+                .iter()
+                .map(|status| status.name())
+                .collect::<Vec<_>>()
+                .into(),
+        ),
+        ("tries", (tries as u64).into()),
+    ])
+    .to_string()
+}
+
+/// Renders the outcome of a game as a line of JSON, e.g.
+/// `{"result":"won","tries":3}` or `{"result":"lost","word":"CRANE"}`.
+#[must_use]
+pub fn json_result(status: &GameStatus) -> String {
+    match status {
+        GameStatus::Won { tries } => {
+            json::object([("result", "won".into()), ("tries", (*tries as u64).into())])
+        }
+        GameStatus::Lost { word } => return json_loss(Some(word)),
+        GameStatus::InProgress => json::object([("result", "in_progress".into())]),
+    }
+    .to_string()
+}
+
+/// Renders a lost game as a line of JSON, with the word unless it is
+/// hidden: `{"result":"lost","word":"CRANE"}` or `{"result":"lost"}`.
+#[must_use]
+pub fn json_loss(word: Option<&str>) -> String {
+    match word {
+        Some(word) => json::object([("result", "lost".into()), ("word", word.into())]),
+        None => json::object([("result", "lost".into())]),
+    }
+    .to_string()
+}
+
+/// Renders a rejected guess or other problem as a line of JSON, e.g.
+/// `{"error":"Your guess must be 5 letters."}`.
+#[must_use]
+pub fn json_error(message: &str) -> String {
+    json::object([("error", Value::from(message))]).to_string()
+}
+
+/// Renders a keyboard for the letters of `alphabet`, with every key shown in
+/// the best status known for it. Letters are laid out as on a QWERTY
+/// keyboard, and letters it doesn't have, like accented ones, go in extra
+/// rows below. Keys that haven't been guessed are left plain; in symbol mode
+/// absent keys are blanked out with `-`. Rows wider than `width` columns
+/// are wrapped onto more lines.
+#[must_use]
+pub fn render_keyboard<S: BuildHasher>(
+    state: &HashMap<char, LetterStatus, S>,
+    alphabet: &[char],
+    mode: RenderMode,
+    theme: Theme,
+    width: usize,
+) -> String {
+    let mut rows: Vec<Vec<char>> = KEYBOARD_ROWS
+        .iter()
+        .map(|row| row.chars().filter(|c| alphabet.contains(c)).collect())
+        .collect();
+    let extra: Vec<char> = alphabet
+        .iter()
+        .filter(|c| !KEYBOARD_ROWS.iter().any(|row| row.contains(**c)))
+        .copied()
+        .collect();
+    rows.extend(extra.chunks(EXTRA_ROW_LENGTH).map(<[char]>::to_vec));
+
+    let mut out = String::new();
+    for (indent, row) in rows.iter().enumerate() {
+        if row.is_empty() {
+            continue;
+        }
+        let indent = indent.min(KEYBOARD_ROWS.len() - 1);
+        let keys: Vec<String> = row
+            .iter()
+            .map(|&key| match (mode, state.get(&key)) {
+                (_, None) => format!(" {key} "),
+                (RenderMode::Color, Some(status)) => {
+                    format!(" {} ", theme.paint(&key.to_string(), *status))
+                }
+                (RenderMode::Symbols | RenderMode::Describe, Some(status)) => match status {
+                    LetterStatus::Correct => format!("[{key}]"),
+                    LetterStatus::Misplaced => format!("({key})"),
+                    LetterStatus::Absent => " - ".to_string(),
+                },
+            })
+            .collect();
+        let keys_per_line = (width.saturating_sub(indent) / CELL_WIDTH).max(1);
+        for line in keys.chunks(keys_per_line) {
+            out.push_str(&" ".repeat(indent));
+            out.push_str(&line.concat());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use colored::Colorize;
+
+    fn guess() -> Guess {
+        Guess {
+            word: "ACD".to_string(),
+            statuses: vec![
+                LetterStatus::Correct,
+                LetterStatus::Misplaced,
+                LetterStatus::Absent,
+            ],
+        }
+    }
+
+    #[test]
+    fn test_render_symbols() {
+        assert_eq!(
+            render_guess(&guess(), RenderMode::Symbols, Theme::default()),
+            "[A](C) D "
+        );
+    }
+
+    #[test]
+    fn test_render_keyboard_symbols() {
+        let state = HashMap::from([
+            ('Q', LetterStatus::Correct),
+            ('A', LetterStatus::Misplaced),
+            ('Z', LetterStatus::Absent),
+        ]);
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+        let keyboard = render_keyboard(
+            &state,
+            &alphabet,
+            RenderMode::Symbols,
+            Theme::default(),
+            DEFAULT_WIDTH,
+        );
+        let rows: Vec<&str> = keyboard.lines().collect();
+        assert!(rows[0].starts_with("[Q] W "));
+        assert!(rows[1].starts_with(" (A) S "));
+        assert!(rows[2].starts_with("   -  X "));
+    }
+
+    #[test]
+    fn test_json_lines() {
+        assert_eq!(
+            json_guess(&guess(), 2),
+            r#"{"guess":"ACD","statuses":["correct","misplaced","absent"],"tries":2}"#
+        );
+        assert_eq!(
+            json_result(&GameStatus::Won { tries: 3 }),
+            r#"{"result":"won","tries":3}"#
+        );
+        assert_eq!(
+            json_result(&GameStatus::Lost {
+                word: "CRANE".to_string()
+            }),
+            r#"{"result":"lost","word":"CRANE"}"#
+        );
+        assert_eq!(json_loss(None), r#"{"result":"lost"}"#);
+        assert_eq!(json_error("Oops"), r#"{"error":"Oops"}"#);
+    }
+
+    #[test]
+    fn test_render_keyboard_extra_letters() {
+        let alphabet: Vec<char> = "AEIOUÁÉÍÓÚÑ".chars().collect();
+        let state = HashMap::from([('Ñ', LetterStatus::Correct)]);
+        let keyboard = render_keyboard(
+            &state,
+            &alphabet,
+            RenderMode::Symbols,
+            Theme::default(),
+            DEFAULT_WIDTH,
+        );
+        let rows: Vec<&str> = keyboard.lines().collect();
+        assert_eq!(rows, vec![" E  U  I  O ", "  A ", "   Á  É  Í  Ó  Ú [Ñ]"]);
+    }
+
+    #[test]
+    fn test_render_keyboard_wraps() {
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+        let state = HashMap::from([('Q', LetterStatus::Correct)]);
+        let keyboard =
+            render_keyboard(&state, &alphabet, RenderMode::Symbols, Theme::default(), 13);
+        let rows: Vec<&str> = keyboard.lines().collect();
+        assert_eq!(rows[0], "[Q] W  E  R ");
+        assert_eq!(rows[2], " O  P ");
+        assert_eq!(rows[3], "  A  S  D  F ");
+        assert!(rows.iter().all(|row| visible_width(row) <= 13));
+        let narrow = render_keyboard(&state, &alphabet, RenderMode::Symbols, Theme::default(), 0);
+        assert_eq!(narrow.lines().count(), 26);
+    }
+
+    #[test]
+    fn test_resolve_mode() {
+        use RenderMode::{Color, Symbols};
+        assert_eq!(RenderMode::resolve(Color, false, true), Color);
+        assert_eq!(RenderMode::resolve(Color, true, true), Symbols);
+        assert_eq!(RenderMode::resolve(Color, false, false), Symbols);
+        assert_eq!(RenderMode::resolve(Symbols, false, true), Symbols);
+        assert_eq!(
+            RenderMode::resolve(RenderMode::Describe, true, false),
+            RenderMode::Describe
+        );
+    }
+
+    #[test]
+    fn test_render_color() {
+        assert_eq!(
+            render_guess(&guess(), RenderMode::Color, Theme::default()),
+            format!(
+                "{} {} {}",
+                "A".bright_green(),
+                "C".bright_yellow(),
+                "D".bright_red()
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_legend() {
+        assert_eq!(
+            render_legend(RenderMode::Symbols, Theme::default()).as_deref(),
+            Some("[A] right spot   (B) wrong spot    C  not in the word")
+        );
+        assert_eq!(render_legend(RenderMode::Describe, Theme::default()), None);
+    }
+
+    #[test]
+    fn test_describe_guess() {
+        use LetterStatus::{Absent, Correct, Misplaced};
+        assert_eq!(
+            describe_guess(&[Correct, Absent, Misplaced, Absent, Correct], "CRANE"),
+            "C correct, R absent, A misplaced, N absent, E correct."
+        );
+        assert_eq!(
+            describe_guess(&[Correct, Correct, Absent, Misplaced], "A BC"),
+            "A correct, B absent, C misplaced."
+        );
+    }
+
+    #[test]
+    fn test_row_prefix() {
+        assert_eq!(row_prefix(Numbering::FromOne, 0, 6, "6"), "1: ");
+        assert_eq!(row_prefix(Numbering::FromOne, 2, 10, "10"), " 3: ");
+        assert_eq!(row_prefix(Numbering::FromOne, 9, 10, "10"), "10: ");
+        assert_eq!(row_prefix(Numbering::FromZero, 0, 6, "6"), "0: ");
+        assert_eq!(row_prefix(Numbering::FromZero, 9, 11, "11"), " 9: ");
+        assert_eq!(row_prefix(Numbering::OfTotal, 1, 6, "6"), "Guess 2/6: ");
+        assert_eq!(row_prefix(Numbering::OfTotal, 3, 12, "∞"), "Guess  4/∞: ");
+    }
+
+    #[test]
+    fn test_render_distance_row() {
+        assert_eq!(
+            render_distance_row("CRANE", 3),
+            " C  R  A  N  E   3 edits away"
+        );
+        assert_eq!(render_distance_row("AB", 1), " A  B   1 edit away");
+    }
+
+    #[test]
+    fn test_visible_width() {
+        assert_eq!(visible_width("abc"), 3);
+        assert_eq!(visible_width("\x1b[1;92mA\x1b[0m"), 1);
+        assert_eq!(visible_width(""), 0);
+    }
+
+    #[test]
+    fn test_rows_have_a_fixed_width() {
+        let color = render_row(&guess(), RenderMode::Color, Theme::default());
+        assert_eq!(visible_width(&color), 3 * CELL_WIDTH);
+        let symbols = render_row(&guess(), RenderMode::Symbols, Theme::default());
+        assert_eq!(symbols, "[A](C) D ");
+        assert_eq!(render_empty_row("ACD"), " ·  ·  · ");
+        assert_eq!(render_empty_row("A D"), " ·     · ");
+    }
+}
@@ -0,0 +1,195 @@
+//! The game being played, kept on disk between guesses so it can be picked
+//! up again later, and other programs, like a status bar, can show how it's
+//! going.
+
+use crate::json::{self, Value};
+use crate::solver::edit_distance;
+use crate::stats::config_dir;
+use crate::{emoji_row, evaluate, Guess, Mode, RustleGame};
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+/// The word and guesses of a game that isn't finished yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedGame {
+    pub word: String,
+    pub guesses: Vec<Guess>,
+    pub max_tries: usize,
+    pub zen: bool,
+    pub hard_mode: bool,
+    pub mode: Mode,
+}
+
+impl SavedGame {
+    /// The state of `game` as it stands now.
+    #[must_use]
+    pub fn of(game: &RustleGame) -> SavedGame {
+        SavedGame {
+            word: game.word().to_string(),
+            guesses: game.guesses().to_vec(),
+            max_tries: game.max_tries(),
+            zen: game.zen(),
+            hard_mode: game.hard_mode(),
+            mode: game.mode(),
+        }
+    }
+
+    /// A one-line summary for a status bar: the last guess with its colored
+    /// squares and how many tries are used, like `CRANE ⬛🟨⬛⬛🟩 2/6`, or
+    /// in distance mode with how far it is from the word instead, like
+    /// `CRANE 2 edits away 2/6`. It never shows the word itself.
+    #[must_use]
+    pub fn status_line(&self) -> String {
+        let tries = if self.zen {
+            "∞".to_string()
+        } else {
+            self.max_tries.to_string()
+        };
+        match self.guesses.last() {
+            Some(guess) if self.mode == Mode::Distance => {
+                let distance = edit_distance(&guess.word, &self.word);
+                let unit = if distance == 1 { "edit" } else { "edits" };
+                format!(
+                    "{} {distance} {unit} away {}/{tries}",
+                    guess.word,
+                    self.guesses.len()
+                )
+            }
+            Some(guess) => format!(
+                "{} {} {}/{tries}",
+                guess.word,
+                emoji_row(&guess.statuses),
+                self.guesses.len()
+            ),
+            None => format!("0/{tries}"),
+        }
+    }
+
+    /// Where the game is kept: `rustle/game.json` in the user's config
+    /// directory, if one can be found.
+    #[must_use]
+    pub fn default_path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("rustle").join("game.json"))
+    }
+
+    /// Reads the game saved at `path`, or `None` if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't a valid saved
+    /// game.
+    pub fn load(path: &Path) -> io::Result<Option<SavedGame>> {
+        json::load_file(path, "a valid game", Self::from_json)
+    }
+
+    /// Writes the game to `path`, creating its directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file or its directory can't be written.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        json::save_file(path, &self.to_json())
+    }
+
+    /// Removes the game saved at `path`, once it is over. There being none
+    /// is fine.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but can't be removed.
+    pub fn clear(path: &Path) -> io::Result<()> {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let guesses: Vec<Value> = self
+            .guesses
+            .iter()
+            .map(|guess| guess.word.as_str().into())
+            .collect();
+        json::object([
+            ("word", self.word.as_str().into()),
+            ("guesses", guesses.into()),
+            ("max_tries", (self.max_tries as u64).into()),
+            ("zen", self.zen.into()),
+            ("hard_mode", self.hard_mode.into()),
+            ("mode", self.mode.name().into()),
+        ])
+    }
+
+    /// Only the guessed words are stored; their statuses are worked out
+    /// again against the word. Games saved before the hard mode and the
+    /// mode were stored are played in the default ones.
+    fn from_json(text: &str) -> Option<SavedGame> {
+        let value = json::parse(text)?;
+        let word = value.get("word")?.as_str()?.to_string();
+        let guesses = value
+            .get("guesses")?
+            .as_array()?
+            .iter()
+            .map(|guess| {
+                let guess = guess.as_str()?.to_string();
+                Some(Guess {
+                    statuses: evaluate(&word, &guess),
+                    word: guess,
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(SavedGame {
+            guesses,
+            max_tries: usize::try_from(value.get("max_tries")?.as_u64()?).ok()?,
+            zen: value.get("zen")?.as_bool()?,
+            hard_mode: value.get("hard_mode").map_or(Some(false), Value::as_bool)?,
+            mode: value.get("mode").map_or(Some(Mode::default()), |mode| {
+                Mode::from_name(mode.as_str()?)
+            })?,
+            word,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn saved(guesses: &[&str]) -> SavedGame {
+        let dictionary: Vec<String> = ["CRANE", "SLATE", "TRACE"].map(String::from).into();
+        let mut game = RustleGame::replay_of("CRANE".to_string(), dictionary);
+        for guess in guesses {
+            game.guess(guess);
+        }
+        SavedGame::of(&game)
+    }
+
+    #[test]
+    fn test_status_line() {
+        assert_eq!(saved(&[]).status_line(), "0/6");
+        assert_eq!(
+            saved(&["SLATE", "TRACE"]).status_line(),
+            "TRACE ⬛🟩🟩🟨🟩 2/6"
+        );
+        let zen = SavedGame {
+            zen: true,
+            ..saved(&["SLATE"])
+        };
+        assert_eq!(zen.status_line(), "SLATE ⬛⬛🟩⬛🟩 1/∞");
+        let distance = SavedGame {
+            mode: Mode::Distance,
+            ..saved(&["SLATE", "TRACE"])
+        };
+        assert_eq!(distance.status_line(), "TRACE 2 edits away 2/6");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let game = saved(&["SLATE", "TRACE"]);
+        assert_eq!(
+            SavedGame::from_json(&game.to_json().to_string()),
+            Some(game)
+        );
+        assert_eq!(SavedGame::from_json("{}"), None);
+    }
+}
@@ -0,0 +1,531 @@
+//! Narrowing down the hidden word from the clues of earlier guesses.
+
+use crate::{evaluate, Guess, LetterStatus, PEEK_PLACEHOLDER};
+use std::collections::{HashMap, HashSet};
+
+/// Whether `candidate` could be the hidden word given `guess`'s clues, i.e.
+/// scoring the guess against it gives exactly the same statuses.
+#[must_use]
+pub fn is_consistent(candidate: &str, guess: &Guess) -> bool {
+    evaluate(candidate, &guess.word) == guess.statuses
+}
+
+/// Keeps the words that fit the clues of every guess in `history`.
+#[must_use]
+pub fn filter_candidates(words: &[String], history: &[Guess]) -> Vec<String> {
+    let constraints = Constraints::from_history(history);
+    words
+        .iter()
+        .filter(|word| constraints.allows(word))
+        .cloned()
+        .collect()
+}
+
+/// What the clues of a game say about the hidden word, letter by letter.
+///
+/// A letter's statuses in one guess say how often it occurs: every green or
+/// yellow copy is one occurrence, and a gray copy means there are no more
+/// than that. So a yellow E next to a gray E means exactly one E, and a gray
+/// letter with no green or yellow copy means none at all.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Constraints {
+    /// The letter known to be at each position.
+    fixed: HashMap<usize, char>,
+    /// The letters known not to be at each position.
+    excluded: HashMap<usize, HashSet<char>>,
+    /// The fewest times each letter occurs.
+    min_count: HashMap<char, usize>,
+    /// The most times each letter occurs, when a gray copy gives it away.
+    max_count: HashMap<char, usize>,
+}
+
+impl Constraints {
+    /// Derives the constraints from every guess in `history`. Peeked
+    /// placeholders say nothing and are skipped.
+    #[must_use]
+    pub fn from_history(history: &[Guess]) -> Self {
+        let mut constraints = Self::default();
+        for guess in history {
+            constraints.add(guess);
+        }
+        constraints
+    }
+
+    fn add(&mut self, guess: &Guess) {
+        let mut found: HashMap<char, usize> = HashMap::new();
+        let mut capped: HashSet<char> = HashSet::new();
+        for (pos, (c, status)) in guess.word.chars().zip(&guess.statuses).enumerate() {
+            if c == PEEK_PLACEHOLDER {
+                continue;
+            }
+            match status {
+                LetterStatus::Correct => {
+                    self.fixed.insert(pos, c);
+                    *found.entry(c).or_insert(0) += 1;
+                }
+                LetterStatus::Misplaced => {
+                    self.excluded.entry(pos).or_default().insert(c);
+                    *found.entry(c).or_insert(0) += 1;
+                }
+                LetterStatus::Absent => {
+                    self.excluded.entry(pos).or_default().insert(c);
+                    capped.insert(c);
+                }
+            }
+        }
+        for (&c, &count) in &found {
+            let min = self.min_count.entry(c).or_insert(0);
+            *min = (*min).max(count);
+        }
+        for c in capped {
+            let count = found.get(&c).copied().unwrap_or(0);
+            let max = self.max_count.entry(c).or_insert(count);
+            *max = (*max).min(count);
+        }
+    }
+
+    /// Whether `word` could be the hidden word.
+    #[must_use]
+    pub fn allows(&self, word: &str) -> bool {
+        let chars: Vec<char> = word.chars().collect();
+        let placed = self
+            .fixed
+            .iter()
+            .all(|(&pos, &c)| chars.get(pos) == Some(&c))
+            && self
+                .excluded
+                .iter()
+                .all(|(&pos, letters)| chars.get(pos).is_none_or(|c| !letters.contains(c)));
+        if !placed {
+            return false;
+        }
+        let count = |c: char| chars.iter().filter(|&&other| other == c).count();
+        self.min_count.iter().all(|(&c, &min)| count(c) >= min)
+            && self.max_count.iter().all(|(&c, &max)| count(c) <= max)
+    }
+}
+
+/// Stands for any letter in the patterns of [`matching`].
+pub const WILDCARD: char = '.';
+
+/// Keeps the words that fit `pattern`, such as `CR.N.`: a letter has to be
+/// at that position, and [`WILDCARD`] can be any letter. Unlike
+/// [`filter_candidates`] it only looks at positions, not at misplaced or
+/// absent letters. The pattern isn't case sensitive.
+#[must_use]
+pub fn matching(words: &[String], pattern: &str) -> Vec<String> {
+    let pattern: Vec<char> = pattern.trim().to_uppercase().chars().collect();
+    words
+        .iter()
+        .filter(|word| {
+            word.chars().count() == pattern.len()
+                && word
+                    .chars()
+                    .zip(&pattern)
+                    .all(|(c, &p)| p == WILDCARD || p == c)
+        })
+        .cloned()
+        .collect()
+}
+
+/// The Levenshtein distance between `a` and `b`: how many letters have to
+/// be inserted, removed or replaced to turn one into the other.
+#[must_use]
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let replace = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = replace.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// How many of `candidates` contain each letter, counting every word once
+/// however often the letter appears in it.
+#[must_use]
+pub fn letter_frequency(candidates: &[String]) -> HashMap<char, usize> {
+    let mut frequency: HashMap<char, usize> = HashMap::new();
+    for word in candidates {
+        for c in word.chars().collect::<HashSet<_>>() {
+            *frequency.entry(c).or_insert(0) += 1;
+        }
+    }
+    frequency
+}
+
+/// How many of `candidates` have each letter at each position, most common
+/// first. Ties are listed alphabetically.
+#[must_use]
+pub fn position_frequency(candidates: &[String]) -> Vec<Vec<(char, usize)>> {
+    let mut positions: Vec<HashMap<char, usize>> = Vec::new();
+    for word in candidates {
+        for (pos, c) in word.chars().enumerate() {
+            if positions.len() <= pos {
+                positions.resize_with(pos + 1, HashMap::new);
+            }
+            *positions[pos].entry(c).or_insert(0) += 1;
+        }
+    }
+    positions
+        .into_iter()
+        .map(|counts| {
+            let mut counts: Vec<(char, usize)> = counts.into_iter().collect();
+            counts.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            counts
+        })
+        .collect()
+}
+
+/// Picks the candidate whose distinct letters are shared by the most other
+/// candidates, so its clues are likely to tell the most apart. Ties go to
+/// the earliest word.
+#[must_use]
+pub fn best_guess(candidates: &[String]) -> Option<String> {
+    let frequency = letter_frequency(candidates);
+
+    let coverage = |word: &String| -> usize {
+        word.chars()
+            .collect::<HashSet<_>>()
+            .iter()
+            .map(|c| frequency[c])
+            .sum()
+    };
+
+    let mut best: Option<(&String, usize)> = None;
+    for word in candidates {
+        let score = coverage(word);
+        if best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((word, score));
+        }
+    }
+    best.map(|(word, _)| word.clone())
+}
+
+/// How many opening guesses [`explain_openings`] looks at, at most.
+pub const EXPLAIN_SAMPLE: usize = 100;
+
+/// How much an opening guess narrows down the answer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Opening {
+    pub guess: String,
+    /// How many answers its clues rule out when the hidden word is the one
+    /// being explained.
+    pub eliminated: usize,
+    /// How many answers its clues rule out on average, over every possible
+    /// hidden word.
+    pub expected: f64,
+}
+
+/// How many of `answers` the clues of `guess` would rule out if the hidden
+/// word were `word`.
+#[must_use]
+pub fn eliminated(word: &str, guess: &str, answers: &[String]) -> usize {
+    let clue = Guess {
+        word: guess.to_string(),
+        statuses: evaluate(word, guess),
+    };
+    let constraints = Constraints::from_history(&[clue]);
+    answers
+        .iter()
+        .filter(|answer| !constraints.allows(answer))
+        .count()
+}
+
+/// How many of `answers` the clues of `guess` rule out on average, if every
+/// answer is equally likely to be the hidden word. Answers that give the
+/// same clues can't be told apart, so each group of `k` of them leaves `k`
+/// candidates `k` times.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn expected_eliminated(guess: &str, answers: &[String]) -> f64 {
+    if answers.is_empty() {
+        return 0.0;
+    }
+    let mut groups: HashMap<Vec<LetterStatus>, usize> = HashMap::new();
+    for answer in answers {
+        *groups.entry(evaluate(answer, guess)).or_insert(0) += 1;
+    }
+    let total = answers.len() as f64;
+    let remaining: f64 = groups.values().map(|&k| (k * k) as f64).sum::<f64>() / total;
+    total - remaining
+}
+
+/// Rates opening guesses against `word`, best first. When there are more
+/// than `sample` guesses, an evenly spread sample of them is rated, since
+/// every guess is scored against every answer.
+#[must_use]
+pub fn explain_openings(
+    word: &str,
+    guesses: &[String],
+    answers: &[String],
+    sample: usize,
+) -> Vec<Opening> {
+    let step = guesses.len().div_ceil(sample.max(1)).max(1);
+    let mut openings: Vec<Opening> = guesses
+        .iter()
+        .step_by(step)
+        .map(|guess| Opening {
+            guess: guess.clone(),
+            eliminated: eliminated(word, guess, answers),
+            expected: expected_eliminated(guess, answers),
+        })
+        .collect();
+    openings.sort_by(|a, b| {
+        b.eliminated
+            .cmp(&a.eliminated)
+            .then(b.expected.total_cmp(&a.expected))
+    });
+    openings
+}
+
+/// How well a guess narrowed down the word, compared with the best guess
+/// that could have been made instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rating {
+    /// It ruled out less than half as many words as the best guess.
+    Poor,
+    /// It ruled out at least half as many words as the best guess.
+    Good,
+    /// It ruled out as many words as the best guess, or more.
+    Great,
+}
+
+impl Rating {
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Rating::Poor => "Poor",
+            Rating::Good => "Good",
+            Rating::Great => "Great",
+        }
+    }
+}
+
+/// Rates a guess that left `after` of `before` candidate words, when the
+/// best guess would have left `best_after` of them.
+#[must_use]
+pub fn rate_guess(before: usize, after: usize, best_after: usize) -> Rating {
+    let eliminated = before.saturating_sub(after);
+    let best_eliminated = before.saturating_sub(best_after);
+    if eliminated >= best_eliminated {
+        Rating::Great
+    } else if eliminated * 2 >= best_eliminated {
+        Rating::Good
+    } else {
+        Rating::Poor
+    }
+}
+
+/// An opening guess with how many answers it leaves on average.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Opener {
+    pub guess: String,
+    /// How many answers still fit its clues on average, if every answer is
+    /// equally likely to be the hidden word.
+    pub remaining: f64,
+}
+
+/// Rates every answer as an opening guess by how many answers its clues
+/// leave on average, fewest first, with ties going to the earlier word.
+/// Every guess is scored against every answer, so with `sample` only an
+/// evenly spread sample of that many answers is used, both as guesses and
+/// as hidden words.
+#[must_use]
+pub fn best_openers(answers: &[String], sample: Option<usize>) -> Vec<Opener> {
+    let step = sample.map_or(1, |sample| answers.len().div_ceil(sample.max(1)).max(1));
+    let answers: Vec<String> = answers.iter().step_by(step).cloned().collect();
+    #[allow(clippy::cast_precision_loss)]
+    let total = answers.len() as f64;
+    let mut openers: Vec<Opener> = answers
+        .iter()
+        .map(|guess| Opener {
+            guess: guess.clone(),
+            remaining: total - expected_eliminated(guess, &answers),
+        })
+        .collect();
+    openers.sort_by(|a, b| a.remaining.total_cmp(&b.remaining));
+    openers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LetterStatus::{Absent, Correct, Misplaced};
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_letter_frequency() {
+        let frequency = letter_frequency(&words(&["EERIE", "CRANE", "SLATE"]));
+        assert_eq!(frequency[&'E'], 3);
+        assert_eq!(frequency[&'R'], 2);
+        assert_eq!(frequency[&'S'], 1);
+        assert!(!frequency.contains_key(&'Z'));
+    }
+
+    #[test]
+    fn test_position_frequency() {
+        let positions = position_frequency(&words(&["CRANE", "CRATE", "GRACE"]));
+        assert_eq!(positions.len(), 5);
+        assert_eq!(positions[0], vec![('C', 2), ('G', 1)]);
+        assert_eq!(positions[1], vec![('R', 3)]);
+        assert_eq!(positions[3], vec![('C', 1), ('N', 1), ('T', 1)]);
+        assert!(position_frequency(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_matching() {
+        let dictionary = words(&["CRANE", "CRONE", "CRANK", "BRINE", "CRAN"]);
+        assert_eq!(
+            matching(&dictionary, "CR.N."),
+            words(&["CRANE", "CRONE", "CRANK"])
+        );
+        assert_eq!(matching(&dictionary, "cr.ne"), words(&["CRANE", "CRONE"]));
+        assert_eq!(matching(&dictionary, "...."), words(&["CRAN"]));
+        assert!(matching(&dictionary, "Z....").is_empty());
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("CRANE", "CRANE"), 0);
+        assert_eq!(edit_distance("FROOT", "FRONT"), 1);
+        assert_eq!(edit_distance("CRANE", "REACT"), 4);
+        assert_eq!(edit_distance("KITTEN", "SITTING"), 3);
+        assert_eq!(edit_distance("", "ABC"), 3);
+        assert_eq!(edit_distance("ÉCOLE", "ECOLE"), 1);
+    }
+
+    #[test]
+    fn test_filter_candidates() {
+        let history = vec![Guess {
+            word: "CLEAN".to_string(),
+            statuses: vec![Correct, Absent, Misplaced, Misplaced, Misplaced],
+        }];
+        let candidates = filter_candidates(
+            &words(&["CRANE", "CANES", "CLEAN", "CHIRP", "OCEAN"]),
+            &history,
+        );
+        assert_eq!(candidates, words(&["CRANE", "CANES"]));
+    }
+
+    fn history(guesses: &[&str], answer: &str) -> Vec<Guess> {
+        guesses
+            .iter()
+            .map(|guess| Guess {
+                word: guess.to_string(),
+                statuses: evaluate(answer, guess),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_duplicate_letter_clues() {
+        // A yellow E and a gray E: exactly one E, and not in either spot.
+        let constraints = Constraints::from_history(&history(&["SPEED"], "ABIDE"));
+        assert!(constraints.allows("ABIDE"));
+        assert!(constraints.allows("OXIDE"));
+        assert!(!constraints.allows("EERIE"));
+        assert!(constraints.allows("GUIDE"));
+        assert!(!constraints.allows("ELDER"));
+
+        // A green L and a gray L: exactly one L, in the known spot.
+        let constraints = Constraints::from_history(&history(&["LLAMA"], "LOFTY"));
+        assert!(constraints.allows("LOFTY"));
+        assert!(!constraints.allows("LOLLY"));
+
+        // Two yellow Es: at least two.
+        let constraints = Constraints::from_history(&history(&["EERIE"], "SHEEP"));
+        assert!(constraints.allows("SHEEP"));
+        assert!(!constraints.allows("SHEAR"));
+    }
+
+    #[test]
+    fn test_constraints_match_scoring() {
+        let dictionary = crate::words_list(5);
+        for (answer, guesses) in [
+            ("ABIDE", ["SPEED", "EERIE", "DODGE"]),
+            ("LLAMA", ["ALARM", "MAMMA", "LLAMA"]),
+            ("CRONY", ["CRANE", "ROOMY", "CORNY"]),
+        ] {
+            let history = history(&guesses, answer);
+            let expected: Vec<String> = dictionary
+                .iter()
+                .filter(|word| history.iter().all(|guess| is_consistent(word, guess)))
+                .cloned()
+                .collect();
+            assert_eq!(filter_candidates(&dictionary, &history), expected);
+            assert!(expected.contains(&answer.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_best_guess() {
+        assert_eq!(best_guess(&[]), None);
+        assert_eq!(
+            best_guess(&words(&["FUZZY", "CRANE", "TRACE", "CRATE"])),
+            Some("TRACE".to_string())
+        );
+    }
+
+    #[test]
+    fn test_eliminated() {
+        let answers = words(&["CRANE", "CANES", "CHIRP", "STORY"]);
+        assert_eq!(eliminated("CRANE", "CLEAN", &answers), 2);
+        assert_eq!(eliminated("CRANE", "CRANE", &answers), 3);
+        assert_eq!(eliminated("CRANE", "FUZZY", &answers), 1);
+    }
+
+    #[test]
+    fn test_expected_eliminated() {
+        let answers = words(&["CRANE", "CANES", "CHIRP", "STORY"]);
+        // Every answer gives CRANE different clues.
+        assert!((expected_eliminated("CRANE", &answers) - 3.0).abs() < 1e-9);
+        // BLUFF tells nothing apart.
+        assert!(expected_eliminated("BLUFF", &answers).abs() < 1e-9);
+        assert!(expected_eliminated("CRANE", &[]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_explain_openings() {
+        let answers = words(&["CRANE", "CANES", "CHIRP", "STORY"]);
+        let openings = explain_openings("CRANE", &answers, &answers, 10);
+        assert_eq!(openings.len(), 4);
+        assert_eq!(openings[0].guess, "CRANE");
+        assert!(openings
+            .windows(2)
+            .all(|pair| pair[0].eliminated >= pair[1].eliminated));
+        assert_eq!(explain_openings("CRANE", &answers, &answers, 2).len(), 2);
+    }
+
+    #[test]
+    fn test_rate_guess() {
+        assert_eq!(rate_guess(100, 10, 10), Rating::Great);
+        assert_eq!(rate_guess(100, 5, 10), Rating::Great);
+        assert_eq!(rate_guess(100, 50, 10), Rating::Good);
+        assert_eq!(rate_guess(100, 60, 10), Rating::Poor);
+        assert_eq!(rate_guess(100, 100, 10), Rating::Poor);
+        assert_eq!(rate_guess(1, 1, 1), Rating::Great);
+    }
+
+    #[test]
+    fn test_best_openers() {
+        let answers = words(&["BLUFF", "CRANE", "CANES", "CHIRP", "STORY"]);
+        let openers = best_openers(&answers, None);
+        assert_eq!(openers.len(), 5);
+        assert_eq!(openers[0].guess, "CRANE");
+        assert!((openers[0].remaining - 1.0).abs() < 1e-9);
+        assert!(openers
+            .windows(2)
+            .all(|pair| pair[0].remaining <= pair[1].remaining));
+        let sampled = best_openers(&answers, Some(2));
+        assert_eq!(sampled.len(), 2);
+        assert!(best_openers(&[], Some(3)).is_empty());
+    }
+}
@@ -0,0 +1,310 @@
+//! Win/loss statistics that are kept between runs.
+
+use crate::json::{self, Value};
+use crate::{format_duration, MAX_TRIES};
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub games_played: u32,
+    pub wins: u32,
+    pub current_streak: u32,
+    pub max_streak: u32,
+    /// `distribution[i]` is the number of games won in `i + 1` guesses.
+    pub distribution: Vec<u32>,
+    /// The fastest win so far.
+    pub best_time: Option<Duration>,
+    /// Words found in zen mode. They are kept apart from the other games,
+    /// since without a limit on the guesses they can't be lost.
+    pub zen_wins: u32,
+    /// All the guesses it took to find them.
+    pub zen_guesses: u64,
+    /// The day (see [`crate::today`]) the daily mulligan was last used.
+    pub last_mulligan: Option<u64>,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self {
+            games_played: 0,
+            wins: 0,
+            current_streak: 0,
+            max_streak: 0,
+            distribution: vec![0; MAX_TRIES],
+            best_time: None,
+            zen_wins: 0,
+            zen_guesses: 0,
+            last_mulligan: None,
+        }
+    }
+}
+
+impl Stats {
+    /// Records a game won in `tries` guesses.
+    pub fn record_win(&mut self, tries: usize) {
+        self.games_played += 1;
+        self.wins += 1;
+        self.current_streak += 1;
+        self.max_streak = self.max_streak.max(self.current_streak);
+        if self.distribution.len() < tries {
+            self.distribution.resize(tries, 0);
+        }
+        if tries > 0 {
+            self.distribution[tries - 1] += 1;
+        }
+    }
+
+    /// Records a word found in zen mode in `tries` guesses. It doesn't
+    /// count towards the games played, the streaks or the distribution.
+    pub fn record_zen_win(&mut self, tries: usize) {
+        self.zen_wins += 1;
+        self.zen_guesses += tries as u64;
+    }
+
+    /// Whether the mulligan for `day` hasn't been used yet.
+    #[must_use]
+    pub fn mulligan_available(&self, day: u64) -> bool {
+        self.last_mulligan != Some(day)
+    }
+
+    /// Records that the mulligan for `day` has been used.
+    pub fn record_mulligan(&mut self, day: u64) {
+        self.last_mulligan = Some(day);
+    }
+
+    /// Keeps `time` as the best time if no win has been faster.
+    pub fn record_time(&mut self, time: Duration) {
+        if self.best_time.is_none_or(|best| time < best) {
+            self.best_time = Some(time);
+        }
+    }
+
+    /// Records a lost game, which breaks the current streak.
+    pub fn record_loss(&mut self) {
+        self.games_played += 1;
+        self.current_streak = 0;
+    }
+
+    /// The percentage of played games that were won.
+    #[must_use]
+    pub fn win_rate(&self) -> f64 {
+        if self.games_played == 0 {
+            0.0
+        } else {
+            f64::from(self.wins) * 100.0 / f64::from(self.games_played)
+        }
+    }
+
+    /// Where the stats live: `rustle/stats.json` in the user's config
+    /// directory, if one can be found.
+    #[must_use]
+    pub fn default_path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("rustle").join("stats.json"))
+    }
+
+    /// Reads stats from `path`. A missing file means no games have been
+    /// played yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or isn't valid stats JSON.
+    pub fn load(path: &Path) -> io::Result<Stats> {
+        Ok(json::load_file(path, "valid stats", Self::from_json)?.unwrap_or_default())
+    }
+
+    /// Writes the stats to `path`, creating its directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file or its directory can't be written.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        json::save_file(path, &self.to_json())
+    }
+
+    fn to_json(&self) -> Value {
+        json::object([
+            ("games_played", u64::from(self.games_played).into()),
+            ("wins", u64::from(self.wins).into()),
+            ("current_streak", u64::from(self.current_streak).into()),
+            ("max_streak", u64::from(self.max_streak).into()),
+            (
+                "distribution",
+                self.distribution
+                    .iter()
+                    .map(|n| u64::from(*n))
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+            (
+                "best_time",
+                self.best_time
+                    .map_or(Value::Null, |time| time.as_secs_f64().into()),
+            ),
+            ("zen_wins", u64::from(self.zen_wins).into()),
+            ("zen_guesses", self.zen_guesses.into()),
+            (
+                "last_mulligan",
+                self.last_mulligan.map_or(Value::Null, Value::from),
+            ),
+        ])
+    }
+
+    fn from_json(text: &str) -> Option<Stats> {
+        let value = json::parse(text)?;
+        let number = |key| {
+            value
+                .get(key)
+                .and_then(Value::as_u64)
+                .and_then(|n| u32::try_from(n).ok())
+        };
+        Some(Stats {
+            games_played: number("games_played")?,
+            wins: number("wins")?,
+            current_streak: number("current_streak")?,
+            max_streak: number("max_streak")?,
+            distribution: value
+                .get("distribution")?
+                .as_array()?
+                .iter()
+                .map(|n| n.as_u64().and_then(|n| u32::try_from(n).ok()))
+                .collect::<Option<_>>()?,
+            best_time: value
+                .get("best_time")
+                .and_then(Value::as_f64)
+                .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok()),
+            zen_wins: number("zen_wins").unwrap_or(0),
+            zen_guesses: value
+                .get("zen_guesses")
+                .and_then(Value::as_u64)
+                .unwrap_or(0),
+            last_mulligan: value.get("last_mulligan").and_then(Value::as_u64),
+        })
+    }
+
+    /// A human readable summary, with the guess distribution drawn as a bar
+    /// chart.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut out = format!(
+            "Played: {}  Win %: {:.0}  Current streak: {}  Max streak: {}\n",
+            self.games_played,
+            self.win_rate(),
+            self.current_streak,
+            self.max_streak
+        );
+        if let Some(best_time) = self.best_time {
+            let _ = writeln!(out, "Best time: {}", format_duration(best_time));
+        }
+        out.push_str("Guess distribution:\n");
+        for (i, count) in self.distribution.iter().enumerate() {
+            let bar = "#".repeat(*count as usize);
+            let _ = writeln!(out, "{}: {}", i + 1, format!("{bar} {count}").trim_start());
+        }
+        if self.zen_wins > 0 {
+            #[allow(clippy::cast_precision_loss)]
+            let average = self.zen_guesses as f64 / f64::from(self.zen_wins);
+            let _ = writeln!(
+                out,
+                "Zen: {} words found, {average:.1} guesses on average",
+                self.zen_wins
+            );
+        }
+        out
+    }
+}
+
+pub(crate) fn config_dir() -> Option<PathBuf> {
+    let non_empty = |var| std::env::var_os(var).filter(|v| !v.is_empty());
+    if cfg!(windows) {
+        non_empty("APPDATA").map(PathBuf::from)
+    } else {
+        non_empty("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| non_empty("HOME").map(|home| PathBuf::from(home).join(".config")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_results() {
+        let mut stats = Stats::default();
+        stats.record_win(3);
+        stats.record_win(1);
+        stats.record_loss();
+        stats.record_win(3);
+        assert_eq!(stats.games_played, 4);
+        assert_eq!(stats.wins, 3);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.max_streak, 2);
+        assert_eq!(stats.distribution, vec![1, 0, 2, 0, 0, 0]);
+        assert!((stats.win_rate() - 75.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_zen_wins_are_kept_apart() {
+        let mut stats = Stats::default();
+        stats.record_win(2);
+        stats.record_zen_win(14);
+        stats.record_zen_win(3);
+        assert_eq!(stats.games_played, 1);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.distribution, vec![0, 1, 0, 0, 0, 0]);
+        assert_eq!((stats.zen_wins, stats.zen_guesses), (2, 17));
+        assert!(stats
+            .render()
+            .contains("Zen: 2 words found, 8.5 guesses on average"));
+    }
+
+    #[test]
+    fn test_one_mulligan_a_day() {
+        let mut stats = Stats::default();
+        assert!(stats.mulligan_available(100));
+        stats.record_mulligan(100);
+        assert!(!stats.mulligan_available(100));
+        assert!(stats.mulligan_available(101));
+    }
+
+    #[test]
+    fn test_record_time_keeps_the_best() {
+        let mut stats = Stats::default();
+        stats.record_time(Duration::from_secs(30));
+        stats.record_time(Duration::from_secs(45));
+        assert_eq!(stats.best_time, Some(Duration::from_secs(30)));
+        stats.record_time(Duration::from_secs(20));
+        assert_eq!(stats.best_time, Some(Duration::from_secs(20)));
+    }
+
+    #[test]
+    fn test_load_without_best_time() {
+        let stats = Stats::from_json(
+            r#"{"games_played":1,"wins":1,"current_streak":1,"max_streak":1,"distribution":[1]}"#,
+        )
+        .unwrap();
+        assert_eq!(stats.best_time, None);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("rustle-stats-{}", std::process::id()))
+            .join("stats.json");
+        assert_eq!(Stats::load(&path).unwrap(), Stats::default());
+
+        let mut stats = Stats::default();
+        stats.record_win(2);
+        stats.record_win(8);
+        stats.record_time(Duration::from_millis(42_500));
+        stats.record_zen_win(9);
+        stats.record_mulligan(20_000);
+        stats.save(&path).unwrap();
+        assert_eq!(Stats::load(&path).unwrap(), stats);
+
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,183 @@
+//! The colors used to show how letters scored.
+
+use crate::LetterStatus;
+use colored::{Color, ColoredString, Colorize};
+
+/// The environment variables that override single colors of a theme, e.g.
+/// `RUSTLE_CORRECT_COLOR="bright blue"`.
+const CORRECT_COLOR_VAR: &str = "RUSTLE_CORRECT_COLOR";
+const MISPLACED_COLOR_VAR: &str = "RUSTLE_MISPLACED_COLOR";
+const ABSENT_COLOR_VAR: &str = "RUSTLE_ABSENT_COLOR";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub correct: Color,
+    pub misplaced: Color,
+    pub absent: Color,
+    /// Whether absent letters are dimmed too, so they fade into the
+    /// background instead of standing out.
+    pub dim_absent: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::CLASSIC
+    }
+}
+
+impl Theme {
+    /// Green, yellow and red.
+    pub const CLASSIC: Theme = Theme {
+        correct: Color::BrightGreen,
+        misplaced: Color::BrightYellow,
+        absent: Color::BrightRed,
+        dim_absent: false,
+    };
+
+    /// Green and yellow, with absent letters in a dimmed gray rather than
+    /// an alarming red.
+    pub const CLASSIC_GRAY: Theme = Theme {
+        correct: Color::BrightGreen,
+        misplaced: Color::BrightYellow,
+        absent: Color::BrightBlack,
+        dim_absent: true,
+    };
+
+    /// Colors that stand far apart on any background.
+    pub const HIGH_CONTRAST: Theme = Theme {
+        correct: Color::BrightWhite,
+        misplaced: Color::BrightCyan,
+        absent: Color::BrightBlack,
+        dim_absent: false,
+    };
+
+    /// Blue and yellow, which stay apart for red-green color blindness.
+    pub const DEUTERANOPIA: Theme = Theme {
+        correct: Color::BrightBlue,
+        misplaced: Color::BrightYellow,
+        absent: Color::BrightBlack,
+        dim_absent: false,
+    };
+
+    /// Every preset, with the name it is looked up by.
+    pub const PRESETS: [(&'static str, Theme); 4] = [
+        ("classic", Self::CLASSIC),
+        ("classic-gray", Self::CLASSIC_GRAY),
+        ("high-contrast", Self::HIGH_CONTRAST),
+        ("deuteranopia", Self::DEUTERANOPIA),
+    ];
+
+    /// The names accepted by [`Theme::from_name`], in the order of
+    /// [`Theme::PRESETS`].
+    pub const NAMES: [&'static str; 4] = [
+        Self::PRESETS[0].0,
+        Self::PRESETS[1].0,
+        Self::PRESETS[2].0,
+        Self::PRESETS[3].0,
+    ];
+
+    /// Looks up a preset by name.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Theme> {
+        Self::PRESETS
+            .into_iter()
+            .find_map(|(preset, theme)| (preset == name).then_some(theme))
+    }
+
+    /// Looks up a preset by name, warning on stderr and falling back to the
+    /// default theme if there is no such preset.
+    #[must_use]
+    pub fn from_name_or_default(name: &str) -> Theme {
+        Self::from_name(name).unwrap_or_else(|| {
+            eprintln!(
+                "Unknown theme {name}, using the classic one. Themes: {}",
+                Self::NAMES.join(", ")
+            );
+            Theme::default()
+        })
+    }
+
+    /// Replaces the colors named by the `RUSTLE_*_COLOR` environment
+    /// variables. Names that aren't colors are reported and ignored.
+    #[must_use]
+    pub fn with_env_overrides(self) -> Theme {
+        let color = |var, default| match std::env::var(var) {
+            Ok(name) => parse_color(&name).unwrap_or_else(|| {
+                eprintln!("Ignoring {var}: {name} isn't a color");
+                default
+            }),
+            Err(_) => default,
+        };
+        Theme {
+            correct: color(CORRECT_COLOR_VAR, self.correct),
+            misplaced: color(MISPLACED_COLOR_VAR, self.misplaced),
+            absent: color(ABSENT_COLOR_VAR, self.absent),
+            ..self
+        }
+    }
+
+    /// The color for letters with `status`.
+    #[must_use]
+    pub fn color(self, status: LetterStatus) -> Color {
+        match status {
+            LetterStatus::Correct => self.correct,
+            LetterStatus::Misplaced => self.misplaced,
+            LetterStatus::Absent => self.absent,
+        }
+    }
+
+    /// `text` in the style for letters with `status`: its color, and dimmed
+    /// if it is absent and the theme dims those.
+    #[must_use]
+    pub fn paint(self, text: &str, status: LetterStatus) -> ColoredString {
+        let painted = text.color(self.color(status));
+        if self.dim_absent && status == LetterStatus::Absent {
+            painted.dimmed()
+        } else {
+            painted
+        }
+    }
+}
+
+/// Parses a color name such as `blue`, `bright green` or `bright_green`.
+fn parse_color(name: &str) -> Option<Color> {
+    name.trim().replace(['_', '-'], " ").parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Theme::from_name("deuteranopia"), Some(Theme::DEUTERANOPIA));
+        for name in Theme::NAMES {
+            assert!(Theme::from_name(name).is_some());
+        }
+    }
+
+    #[test]
+    fn test_unknown_theme_falls_back_to_default() {
+        assert_eq!(Theme::from_name("sepia"), None);
+        assert_eq!(Theme::from_name_or_default("sepia"), Theme::default());
+    }
+
+    #[test]
+    fn test_classic_gray_dims_absent_letters() {
+        let absent = Theme::CLASSIC_GRAY.paint("A", LetterStatus::Absent);
+        assert_eq!(absent.fgcolor(), Some(Color::BrightBlack));
+        assert!(absent.style().contains(colored::Styles::Dimmed));
+        let correct = Theme::CLASSIC_GRAY.paint("A", LetterStatus::Correct);
+        assert!(!correct.style().contains(colored::Styles::Dimmed));
+        let classic = Theme::CLASSIC.paint("A", LetterStatus::Absent);
+        assert_eq!(classic.fgcolor(), Some(Color::BrightRed));
+        assert!(!classic.style().contains(colored::Styles::Dimmed));
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("bright_green"), Some(Color::BrightGreen));
+        assert_eq!(parse_color("Bright Blue"), Some(Color::BrightBlue));
+        assert_eq!(parse_color("orange"), None);
+    }
+}
@@ -0,0 +1,155 @@
+//! A fixed run of games whose results are added up at the end.
+
+use crate::{GameStatus, RustleGame};
+use std::fmt::Write;
+
+/// What the results table shows for a word that is hidden.
+pub const HIDDEN_WORD: &str = "?";
+
+/// The result of one game of a tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    /// The word, or `None` if it wasn't found and the answers are hidden.
+    pub word: Option<String>,
+    /// How many guesses the win took, or `None` if the word wasn't found.
+    pub tries: Option<usize>,
+    pub max_tries: usize,
+}
+
+/// The results of the games played so far, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Tournament {
+    rounds: Vec<Round>,
+}
+
+impl Tournament {
+    /// Adds the result of `game`. A game that isn't won counts as lost, and
+    /// keeps its word to itself if the answers are hidden.
+    pub fn record(&mut self, game: &RustleGame) {
+        let tries = match game.check_status() {
+            GameStatus::Won { tries } => Some(tries),
+            GameStatus::Lost { .. } | GameStatus::InProgress => None,
+        };
+        let word = match tries {
+            Some(_) => Some(game.word()),
+            None => game.revealed_word(),
+        };
+        self.rounds.push(Round {
+            word: word.map(String::from),
+            tries,
+            max_tries: game.max_tries(),
+        });
+    }
+
+    #[must_use]
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    /// How many of the games were won.
+    #[must_use]
+    pub fn wins(&self) -> usize {
+        self.rounds
+            .iter()
+            .filter(|round| round.tries.is_some())
+            .count()
+    }
+
+    /// The average number of guesses per win, if any game was won.
+    #[must_use]
+    pub fn average_tries(&self) -> Option<f64> {
+        let wins = self.wins();
+        if wins == 0 {
+            return None;
+        }
+        let total: usize = self.rounds.iter().filter_map(|round| round.tries).sum();
+        #[allow(clippy::cast_precision_loss)]
+        Some(total as f64 / wins as f64)
+    }
+
+    /// A table of every game with its word and score, followed by the
+    /// totals. Hidden words are shown as [`HIDDEN_WORD`].
+    #[must_use]
+    pub fn render(&self) -> String {
+        let number_width = self.rounds.len().to_string().len();
+        let word_width = self
+            .rounds
+            .iter()
+            .map(|round| round.word.as_deref().unwrap_or(HIDDEN_WORD).chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut out = "Tournament results:\n".to_string();
+        for (i, round) in self.rounds.iter().enumerate() {
+            let score = round
+                .tries
+                .map_or_else(|| "X".to_string(), |tries| tries.to_string());
+            let _ = writeln!(
+                out,
+                "{:>number_width$}. {:<word_width$}  {score}/{}",
+                i + 1,
+                round.word.as_deref().unwrap_or(HIDDEN_WORD),
+                round.max_tries
+            );
+        }
+        let _ = write!(out, "Won {} of {}", self.wins(), self.rounds.len());
+        if let Some(average) = self.average_tries() {
+            let _ = write!(out, ", {average:.1} guesses per win on average");
+        }
+        out.push('\n');
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let dictionary: Vec<String> = ["CRANE", "SLATE", "GRACE"].map(String::from).into();
+        let mut tournament = Tournament::default();
+        assert_eq!(tournament.render(), "Tournament results:\nWon 0 of 0\n");
+
+        let mut won = RustleGame::replay_of("CRANE".to_string(), dictionary.clone());
+        won.guess("SLATE");
+        won.guess("CRANE");
+        tournament.record(&won);
+        let mut lost = RustleGame::replay_of("GRACE".to_string(), dictionary);
+        lost.guess("SLATE");
+        tournament.record(&lost);
+
+        assert_eq!(tournament.wins(), 1);
+        assert_eq!(tournament.average_tries(), Some(2.0));
+        assert_eq!(
+            tournament.render(),
+            "Tournament results:\n\
+             1. CRANE  2/6\n\
+             2. GRACE  X/6\n\
+             Won 1 of 2, 2.0 guesses per win on average\n"
+        );
+    }
+
+    #[test]
+    fn test_hidden_words_stay_hidden() {
+        let dictionary: Vec<String> = ["CRANE", "SLATE", "GRACE"].map(String::from).into();
+        let mut tournament = Tournament::default();
+        let mut won =
+            RustleGame::replay_of("CRANE".to_string(), dictionary.clone()).with_hide_answer(true);
+        won.guess("CRANE");
+        tournament.record(&won);
+        let mut lost = RustleGame::replay_of("GRACE".to_string(), dictionary)
+            .with_max_tries(1)
+            .with_hide_answer(true);
+        lost.guess("SLATE");
+        tournament.record(&lost);
+
+        assert_eq!(tournament.rounds()[1].word, None);
+        assert_eq!(
+            tournament.render(),
+            "Tournament results:\n\
+             1. CRANE  1/6\n\
+             2. ?      X/1\n\
+             Won 1 of 2, 1.0 guesses per win on average\n"
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! A record of finished games, one line of JSON per game, for looking back
+//! at how they were played.
+
+use crate::json::{self, Value};
+use crate::{GameStatus, Guess, RustleGame};
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// One finished game: the word, every guess with its statuses, and how it
+/// ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transcript {
+    pub word: String,
+    pub guesses: Vec<Guess>,
+    pub status: GameStatus,
+    /// When the game ended, in seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+impl Transcript {
+    /// The transcript of `game` as it stands now.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the system clock is set before the Unix epoch.
+    #[must_use]
+    pub fn of(game: &RustleGame) -> Transcript {
+        Transcript {
+            word: game.word().to_string(),
+            guesses: game.guesses().to_vec(),
+            status: game.check_status(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        }
+    }
+
+    /// The transcript as a single line of JSON, without the line break.
+    /// A game that was given up on before it ended has the result
+    /// `gave_up`.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let guesses: Vec<Value> = self
+            .guesses
+            .iter()
+            .map(|guess| {
+                json::object([
+                    ("guess", guess.word.as_str().into()),
+                    (
+                        "statuses",
+                        guess
+                            .statuses
+                            .iter()
+                            .map(|status| status.name())
+                            .collect::<Vec<_>>()
+                            .into(),
+                    ),
+                ])
+            })
+            .collect();
+        let (result, tries) = match self.status {
+            GameStatus::Won { tries } => ("won", Value::from(tries as u64)),
+            GameStatus::Lost { .. } => ("lost", Value::Null),
+            GameStatus::InProgress => ("gave_up", Value::Null),
+        };
+        json::object([
+            ("timestamp", self.timestamp.into()),
+            ("word", self.word.as_str().into()),
+            ("guesses", guesses.into()),
+            ("result", result.into()),
+            ("tries", tries),
+        ])
+        .to_string()
+    }
+
+    /// Adds the transcript as a line at the end of the file at `path`,
+    /// creating it if needed. The line goes out in a single write to a file
+    /// opened for appending, so games logged at the same time by several
+    /// runs don't end up mixed into each other.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be opened or written.
+    pub fn append_to(&self, path: &Path) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(format!("{}\n", self.to_json()).as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn transcript(guesses: &[&str]) -> Transcript {
+        let dictionary: Vec<String> = ["CRANE", "SLATE"].map(String::from).into();
+        let mut game = RustleGame::replay_of("CRANE".to_string(), dictionary);
+        for guess in guesses {
+            game.guess(guess);
+        }
+        Transcript {
+            timestamp: 1_700_000_000,
+            ..Transcript::of(&game)
+        }
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            transcript(&["SLATE", "CRANE"]).to_json(),
+            r#"{"timestamp":1700000000,"word":"CRANE","guesses":[{"guess":"SLATE","statuses":["absent","absent","correct","absent","correct"]},{"guess":"CRANE","statuses":["correct","correct","correct","correct","correct"]}],"result":"won","tries":2}"#
+        );
+        assert!(transcript(&["SLATE"])
+            .to_json()
+            .ends_with(r#""result":"gave_up","tries":null}"#));
+    }
+
+    #[test]
+    fn test_append_to() {
+        let dir = std::env::temp_dir().join(format!("rustle-log-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("games.jsonl");
+        let won = transcript(&["CRANE"]);
+        let lost = transcript(&["SLATE"; 6]);
+        won.append_to(&path).unwrap();
+        lost.append_to(&path).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!("{}\n{}\n", won.to_json(), lost.to_json())
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
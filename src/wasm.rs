@@ -0,0 +1,37 @@
+//! Headless entry points for a WebAssembly build, enabled with the `wasm`
+//! feature and exported to JavaScript with `wasm-bindgen`.
+//!
+//! Everything here only scores words in memory: no stdin, stdout, files or
+//! terminal colors. A browser build has to leave out the rest of the crate's
+//! terminal code (`colored`, the keyboard and prompt rendering, `main`) and
+//! call these functions instead.
+
+use crate::{evaluate, sanitize_word, LetterStatus};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Scores `guess` against `target`, one byte per letter: `2` for correct,
+/// `1` for misplaced and `0` for absent. Both words are sanitized first, so
+/// case doesn't matter. In JavaScript it returns a `Uint8Array`.
+#[must_use]
+#[wasm_bindgen]
+pub fn score(target: &str, guess: &str) -> Vec<u8> {
+    evaluate(&sanitize_word(target), &sanitize_word(guess))
+        .into_iter()
+        .map(|status| match status {
+            LetterStatus::Correct => 2,
+            LetterStatus::Misplaced => 1,
+            LetterStatus::Absent => 0,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        assert_eq!(score("crane", "CANES"), vec![2, 1, 1, 1, 0]);
+        assert_eq!(score("CRANE", "crane"), vec![2; 5]);
+    }
+}
@@ -0,0 +1,264 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// A config directory holding `rustle/config.toml` with `config`, named
+/// after the test so tests running at the same time don't share it.
+fn config_home(test: &str, config: &str) -> PathBuf {
+    let home = std::env::temp_dir().join(format!("rustle-cli-{test}-{}", std::process::id()));
+    std::fs::create_dir_all(home.join("rustle")).unwrap();
+    std::fs::write(home.join("rustle").join("config.toml"), config).unwrap();
+    home
+}
+
+/// Runs the game with `args` and `RUSTLE_ANSWER` set to `answer`, typing
+/// the lines of `input`, and returns the exit code and everything it
+/// printed.
+fn run_with_answer(
+    home: &PathBuf,
+    args: &[&str],
+    input: &str,
+    answer: Option<&str>,
+) -> (i32, String) {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_rustle"));
+    command
+        .args(args)
+        .env("XDG_CONFIG_HOME", home)
+        .env("NO_COLOR", "1")
+        .env_remove("RUSTLE_ANSWER");
+    if let Some(answer) = answer {
+        command.env("RUSTLE_ANSWER", answer);
+    }
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // The game may stop before reading it all, e.g. on a usage error.
+    if let Err(e) = child.stdin.take().unwrap().write_all(input.as_bytes()) {
+        assert_eq!(e.kind(), std::io::ErrorKind::BrokenPipe, "{e}");
+    }
+    let output = child.wait_with_output().unwrap();
+    let text = String::from_utf8_lossy(&output.stdout).into_owned()
+        + &String::from_utf8_lossy(&output.stderr);
+    (output.status.code().unwrap(), text)
+}
+
+/// Like [`run_with_answer`], without `RUSTLE_ANSWER`.
+fn run_with_input(home: &PathBuf, args: &[&str], input: &str) -> (i32, String) {
+    run_with_answer(home, args, input, None)
+}
+
+/// Like [`run_with_input`], quitting at the first prompt.
+fn run(home: &PathBuf, args: &[&str]) -> (i32, String) {
+    run_with_input(home, args, "/quit\n")
+}
+
+const QUIT: i32 = 2;
+
+#[test]
+fn config_defaults_dont_clash_with_modes_that_fix_them() {
+    let home = config_home("modes", "length = 6\ntries = 8\nhard = true\n");
+    for args in [
+        &["--daily"][..],
+        &["--zen"],
+        &["--dordle"],
+        &["--mode", "distance"],
+    ] {
+        let (code, output) = run(&home, args);
+        assert_eq!(code, QUIT, "rustle {args:?} failed:\n{output}");
+    }
+    let (_, output) = run(&home, &["--daily"]);
+    assert!(output.contains("(5 letters)"), "{output}");
+    let (_, output) = run(&home, &[]);
+    assert!(output.contains("(6 letters)"), "{output}");
+    assert!(output.contains("Guess 1 of 8."), "{output}");
+    let (_, output) = run(&home, &["--dordle"]);
+    assert!(output.contains("Guess 1 of 7."), "{output}");
+    std::fs::remove_dir_all(home).unwrap();
+}
+
+#[test]
+fn flags_win_over_the_config() {
+    let home = config_home("flags", "tries = 8\nhard = true\n");
+    let args = ["--tries", "4", "--answer", "crane"];
+    let input = "slate\npilot\n/quit\n";
+    let (_, output) = run_with_input(&home, &args, input);
+    assert!(output.contains("Guess 1 of 4."), "{output}");
+    assert!(output.contains("Must use A"), "{output}");
+    let (code, output) = run_with_input(&home, &[&args[..], &["--no-hard"]].concat(), input);
+    assert_eq!(code, QUIT, "{output}");
+    assert!(!output.contains("Must use A"), "{output}");
+    assert!(output.contains("Guess 3 of 4."), "{output}");
+    let (code, output) = run(&home, &["--zen", "--tries", "4"]);
+    assert_eq!(
+        code, 3,
+        "--zen and --tries given together should clash:\n{output}"
+    );
+    std::fs::remove_dir_all(home).unwrap();
+}
+
+#[test]
+fn hidden_answers_stay_hidden_in_every_mode() {
+    let home = config_home("hidden", "");
+    let hidden =
+        |extra: &[&'static str]| [&["--answer", "crane", "--hide-answer"][..], extra].concat();
+    let lose = |tries: usize| "slate\n".repeat(tries);
+
+    let (code, output) = run(&home, &hidden(&["--auto", "--tries", "1"]));
+    assert_eq!(code, 1, "{output}");
+    assert!(output.contains("Failed to solve it!"), "{output}");
+    assert!(!output.contains("CRANE"), "{output}");
+
+    let (_, output) = run_with_input(&home, &hidden(&[]), "/reveal\n");
+    assert!(output.contains("You gave up."), "{output}");
+    assert!(!output.contains("CRANE"), "{output}");
+
+    for input in ["/reveal\n".to_string(), lose(6)] {
+        let (code, output) = run_with_input(&home, &hidden(&["--json"]), &input);
+        assert_eq!(code, 1, "{output}");
+        assert!(output.ends_with("{\"result\":\"lost\"}\n"), "{output}");
+    }
+
+    let (_, output) = run_with_input(&home, &hidden(&["--dordle"]), "/reveal\n");
+    assert!(output.contains("You gave up."), "{output}");
+    let (code, output) = run_with_input(&home, &hidden(&["--dordle"]), &lose(7));
+    assert_eq!(code, 1, "{output}");
+    assert!(output.contains("Out of tries!"), "{output}");
+    assert!(!output.contains("CRANE"), "{output}");
+
+    let tournament = [
+        "--tournament",
+        "2",
+        "--auto",
+        "--tries",
+        "1",
+        "--seed",
+        "42",
+    ];
+    let (_, output) = run(&home, &[&tournament[..], &["--hide-answer"]].concat());
+    assert!(output.contains("1. ?  X/1"), "{output}");
+    assert!(!output.contains("SPAED"), "{output}");
+    let (_, output) = run(&home, &tournament);
+    assert!(output.contains("1. SPAED  X/1"), "{output}");
+
+    let (code, _) = run(&home, &hidden(&["--explain"]));
+    assert_eq!(code, 3);
+    std::fs::remove_dir_all(home).unwrap();
+}
+
+#[test]
+fn answer_variable_only_fixes_the_first_unseeded_game() {
+    let home = config_home("answer", "");
+    let (_, output) = run_with_answer(&home, &[], "/reveal\n", Some("crane"));
+    assert!(output.contains("The word was CRANE"), "{output}");
+
+    let (_, output) = run_with_answer(&home, &["--seed", "42"], "/reveal\n", Some("crane"));
+    assert!(output.contains("The word was SPAED"), "{output}");
+
+    let (_, output) = run_with_answer(&home, &["--answer", "slate"], "/reveal\n", Some("crane"));
+    assert!(output.contains("The word was SLATE"), "{output}");
+
+    let (_, output) = run_with_answer(&home, &[], "/reveal\n", Some("cranx"));
+    assert_eq!(
+        output.matches("Not using RUSTLE_ANSWER=cranx").count(),
+        1,
+        "{output}"
+    );
+
+    let (_, output) = run_with_answer(&home, &["--tournament", "2", "--auto"], "", Some("cranx"));
+    assert!(!output.contains("RUSTLE_ANSWER"), "{output}");
+    std::fs::remove_dir_all(home).unwrap();
+}
+
+#[test]
+fn seeds_work_with_custom_word_lists() {
+    let home = config_home("seed", "");
+    let dict = home.join("words.txt");
+    std::fs::write(&dict, "crane\nslate\ngrace\nsloth\nbrave\n").unwrap();
+    let dict = dict.to_str().unwrap();
+    for args in [
+        &["--dict", dict][..],
+        &["--length", "6"],
+        &["--answers", dict],
+    ] {
+        let args = [args, &["--seed", "7"]].concat();
+        let (code, first) = run_with_input(&home, &args, "/reveal\n");
+        assert_eq!(code, 1, "rustle {args:?} failed:\n{first}");
+        let (_, second) = run_with_input(&home, &args, "/reveal\n");
+        let word = |output: &str| {
+            output
+                .lines()
+                .find(|l| l.starts_with("The word was"))
+                .map(String::from)
+        };
+        assert!(word(&first).is_some(), "{first}");
+        assert_eq!(word(&first), word(&second));
+    }
+    std::fs::remove_dir_all(home).unwrap();
+}
+
+#[test]
+fn distance_mode_gives_no_letters_away() {
+    let home = config_home("distance", "");
+    for flag in ["--assist", "--coach", "--hints", "--freqs"] {
+        let (code, output) = run(&home, &["--mode", "distance", flag]);
+        assert_eq!(code, 3, "{flag}: {output}");
+        assert!(output.contains("--mode distance doesn't score letters"));
+    }
+
+    let (code, output) = run_with_input(
+        &home,
+        &["--mode", "distance", "--answer", "crane"],
+        "slate\n/hint\n/quit\n",
+    );
+    assert_eq!(code, QUIT, "{output}");
+    assert!(output.contains("/hint would give letters away"), "{output}");
+    assert!(!output.contains("Try "), "{output}");
+    let (_, status) = run(&home, &["--status-line"]);
+    assert_eq!(status.trim(), "SLATE 3 edits away 1/6");
+    std::fs::remove_dir_all(home).unwrap();
+}
+
+#[test]
+fn replays_are_not_recorded() {
+    let home = config_home("replay", "");
+    let (code, output) = run_with_input(
+        &home,
+        &["--answer", "crane", "--tries", "1"],
+        "slate\n/replay\ncrane\n",
+    );
+    assert_eq!(code, 1, "{output}");
+    assert!(output.contains("Correct!"), "{output}");
+    assert!(!output.contains("scored"), "{output}");
+    let (_, stats) = run(&home, &["--stats"]);
+    assert!(stats.contains("Played: 1  Win %: 0"), "{stats}");
+    assert!(!stats.contains("points"), "{stats}");
+
+    let (_, daily) = run_with_input(&home, &["--daily", "--tries", "1"], "slate\n/replay\n");
+    assert_eq!(daily.matches("Proof:").count(), 1, "{daily}");
+    assert!(!daily.contains("/replay"), "{daily}");
+    std::fs::remove_dir_all(home).unwrap();
+}
+
+#[test]
+fn daily_and_tournament_games_are_not_saved() {
+    for args in [&["--daily"][..], &["--tournament", "2"]] {
+        let home = config_home("unsaved", "");
+        let (code, output) = run_with_input(&home, args, "slate\n/quit\n");
+        assert_eq!(code, QUIT, "{args:?}: {output}");
+        let (_, status) = run(&home, &["--status-line"]);
+        assert_eq!(status, "", "{args:?}");
+        let (_, resumed) = run(&home, &["--resume"]);
+        assert!(!resumed.contains("Picking up"), "{args:?}: {resumed}");
+        std::fs::remove_dir_all(home).unwrap();
+    }
+
+    let home = config_home("saved", "");
+    run_with_input(&home, &["--answer", "crane"], "slate\n/quit\n");
+    run_with_input(&home, &["--daily"], "/reveal\n");
+    let (_, status) = run(&home, &["--status-line"]);
+    assert!(status.starts_with("SLATE "), "{status}");
+    std::fs::remove_dir_all(home).unwrap();
+}
@@ -0,0 +1,107 @@
+use rustle::{GameStatus, GuessAction, InvalidGuess, RustleGame};
+
+#[test]
+fn seed_picks_a_reproducible_word() {
+    assert_eq!(RustleGame::with_seed(42).unwrap().word(), "SPAED");
+    assert_eq!(
+        RustleGame::with_seed(7).unwrap().word(),
+        RustleGame::with_seed(7).unwrap().word()
+    );
+}
+
+#[test]
+fn guesses_can_be_driven_without_stdin() {
+    let mut game = RustleGame::with_seed(42).unwrap();
+    game.guess("SPADE");
+    game.guess("SPAED");
+    assert_eq!(game.guesses().len(), 2);
+    assert_eq!(game.guesses()[1].word, "SPAED");
+}
+
+#[test]
+fn autoplay_solves_every_seeded_game() {
+    for seed in 0..5 {
+        let mut game = RustleGame::with_seed(seed).unwrap().with_max_tries(20);
+        let result = game.autoplay();
+        assert!(result.won);
+        assert_eq!(result.tries, game.guesses().len());
+        assert_eq!(game.guesses().last().unwrap().word, game.word());
+    }
+}
+
+#[test]
+fn guessing_the_word_first_counts_one_try() {
+    let mut game = RustleGame::with_seed(42).unwrap();
+    assert_eq!(game.submit("spaed"), Ok(GameStatus::Won { tries: 1 }));
+    assert_eq!(game.guesses().len(), 1);
+}
+
+#[test]
+fn reported_tries_match_the_guesses_made() {
+    let mut game = RustleGame::with_seed(42).unwrap();
+    assert_eq!(game.submit("crane"), Ok(GameStatus::InProgress));
+    assert_eq!(
+        game.submit("spa"),
+        Err(InvalidGuess::WrongLength { expected: 5 })
+    );
+    assert_eq!(game.submit("slate"), Ok(GameStatus::InProgress));
+    let status = game.submit("SPAED").unwrap();
+    assert_eq!(status, GameStatus::Won { tries: 3 });
+    assert_eq!(game.guesses().len(), 3);
+}
+
+#[test]
+fn guessing_the_word_on_the_last_try_wins() {
+    let mut game = RustleGame::with_word("crane", rustle::words_list(5)).unwrap();
+    for guess in ["slate", "brave", "grace", "trace", "space"] {
+        assert_eq!(game.submit(guess), Ok(GameStatus::InProgress));
+    }
+    assert_eq!(game.remaining_guesses(), 1);
+    assert_eq!(game.submit("crane"), Ok(GameStatus::Won { tries: 6 }));
+    assert_eq!(game.check_status(), GameStatus::Won { tries: 6 });
+    assert!(game.share_grid().starts_with("Rustle 6/6"));
+}
+
+#[test]
+fn full_playthrough_with_a_chosen_word() {
+    let mut game = RustleGame::with_word("crane", rustle::words_list(5))
+        .unwrap()
+        .with_max_tries(3);
+    assert_eq!(game.submit("slate"), Ok(GameStatus::InProgress));
+    assert_eq!(game.submit("brave"), Ok(GameStatus::InProgress));
+    assert_eq!(
+        game.submit("grace"),
+        Ok(GameStatus::Lost {
+            word: "CRANE".to_string()
+        })
+    );
+
+    let mut replay = game.replay();
+    assert_eq!(replay.submit("crane"), Ok(GameStatus::Won { tries: 1 }));
+}
+
+#[test]
+fn guesses_can_be_read_from_any_input() {
+    colored::control::set_override(false);
+    let mut game = RustleGame::with_word("crane", rustle::words_list(5)).unwrap();
+    let mut input = std::io::Cursor::new("zzzzz\n/undo\nslate\ncrane\n");
+    let mut output = Vec::new();
+
+    let action = game.ask_for_guess_from(&mut input, &mut output).unwrap();
+    assert_eq!(action, GuessAction::Word("SLATE".to_string()));
+    let action = game.ask_for_guess_from(&mut input, &mut output).unwrap();
+    assert_eq!(action, GuessAction::Word("CRANE".to_string()));
+    assert_eq!(game.check_status(), GameStatus::Won { tries: 2 });
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("Guess 1 of 6."));
+    assert!(output.contains("ZZZZZ isn't in the Rustle dictionary."));
+    assert!(output.contains("There is no guess to undo."));
+    assert!(output.contains("Guess 2 of 6."));
+    assert!(output.contains("Letters not in the word: L S T"));
+    assert_eq!(
+        game.ask_for_guess_from(&mut input, std::io::sink())
+            .unwrap(),
+        GuessAction::Quit
+    );
+}